@@ -0,0 +1,191 @@
+use std::fmt::Debug;
+use std::io;
+
+use crate::{Erep, Ereport, ReportSink};
+
+/// Collects reports imperatively, for functions which are not naturally written with
+/// `Erep::map`.
+///
+/// # Example
+/// ```rust
+/// use erep::{Accumulator, Ereport};
+///
+/// fn parse(input: &str) -> erep::Erep<Vec<u8>> {
+///     let mut acc = Accumulator::new();
+///
+///     let bytes = acc.scope("parsing bytes", |acc| {
+///         input
+///             .split(',')
+///             .filter_map(|s| acc.check(s.trim().parse::<u8>()))
+///             .collect::<Vec<_>>()
+///     });
+///
+///     if bytes.is_empty() {
+///         acc.push(Ereport::warning("no bytes given"));
+///     }
+///
+///     acc.finish(bytes)
+/// }
+///
+/// let (bytes, rep) = parse("1, 2, 300, x").unwrap_with_err();
+/// let rep = rep.unwrap();
+///
+/// assert_eq!(bytes, vec![1, 2]);
+/// assert_eq!(rep.message(), "parsing bytes");
+/// assert_eq!(rep.children().len(), 2);
+///
+/// assert!(parse("1").is_clean());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accumulator {
+    reps: Vec<Ereport>,
+}
+
+impl Accumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, rep: Ereport) {
+        self.reps.push(rep);
+    }
+
+    /// Keeps the value on success, otherwise records the error, using its `Debug` output, and
+    /// returns `None`.
+    #[track_caller]
+    pub fn check<T, E>(&mut self, result: Result<T, E>) -> Option<T>
+    where
+        E: Debug,
+    {
+        match result {
+            Ok(val) => Some(val),
+            Err(e) => {
+                self.push(Ereport::new(format!("{e:?}")));
+                None
+            }
+        }
+    }
+
+    /// Keeps the value if there is one, otherwise records a report with the given message.
+    #[track_caller]
+    pub fn check_opt<T, S>(&mut self, option: Option<T>, msg: S) -> Option<T>
+    where
+        S: Into<String>,
+    {
+        if option.is_none() {
+            self.push(Ereport::new(msg));
+        }
+        option
+    }
+
+    /// Records the report of an `Erep`, and returns its value.
+    pub fn take<T>(&mut self, erep: Erep<T>) -> T {
+        let (val, rep) = erep.unwrap_with_err();
+        if let Some(rep) = rep {
+            self.push(rep);
+        }
+        val
+    }
+
+    /// Runs `f` with a fresh accumulator. Anything it records is pushed as the children of a
+    /// single report with the given message, which has the highest severity among them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Accumulator, Ereport, Severity};
+    ///
+    /// let mut acc = Accumulator::new();
+    /// acc.scope("loading users", |acc| acc.push(Ereport::warning("user `bob` has no email")));
+    ///
+    /// let users = acc.finish(());
+    /// assert!(!users.has_errors());
+    /// assert_eq!(users.report().unwrap().severity(), Severity::Warning);
+    /// ```
+    #[track_caller]
+    pub fn scope<S, F, R>(&mut self, msg: S, f: F) -> R
+    where
+        S: Into<String>,
+        F: FnOnce(&mut Accumulator) -> R,
+    {
+        let mut inner = Accumulator::new();
+        let val = f(&mut inner);
+
+        if !inner.is_empty() {
+            let rep = Ereport::new(msg);
+            self.push(Ereport {
+                severity: Ereport::wrapping_severity(&inner.reps),
+                stack: inner.reps,
+                ..rep
+            });
+        }
+        val
+    }
+
+    /// Sends everything recorded so far into the sink, in order, and flushes it, leaving
+    /// the accumulator empty.
+    ///
+    /// If the sink fails, the failing report and the ones after it are kept, so nothing is
+    /// lost, and they can be flushed again later.
+    pub fn flush_into<S>(&mut self, sink: &mut S) -> io::Result<()>
+    where
+        S: ReportSink + ?Sized,
+    {
+        for (i, rep) in self.reps.iter().enumerate() {
+            if let Err(e) = sink.accept(rep) {
+                self.reps.drain(..i);
+                return Err(e);
+            }
+        }
+        self.reps.clear();
+        sink.flush()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reps.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.reps.len()
+    }
+
+    /// The reports recorded so far, in order.
+    pub fn reports(&self) -> &[Ereport] {
+        &self.reps
+    }
+
+    /// Whether any recorded report, or any of their children, is an error.
+    pub fn has_errors(&self) -> bool {
+        self.reps.iter().any(Ereport::has_errors)
+    }
+
+    /// Returns the value, together with everything recorded.
+    #[track_caller]
+    pub fn finish<T>(self, val: T) -> Erep<T> {
+        match Ereport::group(self.reps) {
+            Some(rep) => Erep::with_report(val, rep),
+            None => Erep::ok(val),
+        }
+    }
+
+    /// Returns `Err` with everything recorded, if anything was recorded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Accumulator;
+    ///
+    /// let mut acc = Accumulator::new();
+    /// assert_eq!(acc.check_opt(Some(1), "missing"), Some(1));
+    /// assert!(acc.clone().into_result().is_ok());
+    ///
+    /// acc.check_opt(None::<i32>, "missing");
+    /// assert_eq!(acc.into_result().unwrap_err().to_string(), "missing");
+    /// ```
+    #[track_caller]
+    #[allow(clippy::result_large_err)]
+    pub fn into_result(self) -> Result<(), Ereport> {
+        match Ereport::group(self.reps) {
+            Some(rep) => Err(rep),
+            None => Ok(()),
+        }
+    }
+}
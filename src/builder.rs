@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use crate::{capture, Ereport, Severity, Span};
+
+/// Builds an `Ereport` in one expression, created by `Ereport::builder`.
+///
+/// The location and backtrace are captured where `build` is called.
+///
+/// # Example
+/// ```rust
+/// use erep::{Ereport, Severity};
+///
+/// let rep = Ereport::builder("could not load config")
+///     .severity(Severity::Warning)
+///     .code("E0002")
+///     .metadata("path", "erep.toml")
+///     .child(Ereport::new("file not found"))
+///     .build();
+///
+/// assert_eq!(rep.message(), "could not load config");
+/// assert_eq!(rep.severity(), Severity::Warning);
+/// assert_eq!(rep.code(), Some("E0002"));
+/// assert_eq!(rep.metadata()["path"], "erep.toml");
+/// assert_eq!(rep.children()[0].message(), "file not found");
+/// ```
+#[derive(Debug, Clone)]
+pub struct EreportBuilder {
+    msg: String,
+    stack: Vec<Ereport>,
+    severity: Severity,
+    code: Option<String>,
+    spans: Vec<Span>,
+    metadata: BTreeMap<String, String>,
+}
+
+impl EreportBuilder {
+    pub fn new<S>(msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            msg: msg.into(),
+            stack: Vec::new(),
+            severity: Severity::default(),
+            code: None,
+            spans: Vec::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn message<S>(self, msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            msg: msg.into(),
+            ..self
+        }
+    }
+
+    pub fn child(mut self, rep: Ereport) -> Self {
+        self.stack.push(rep);
+        self
+    }
+
+    pub fn children<I>(mut self, reps: I) -> Self
+    where
+        I: IntoIterator<Item = Ereport>,
+    {
+        self.stack.extend(reps);
+        self
+    }
+
+    pub fn severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    pub fn code<S>(self, code: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            code: Some(code.into()),
+            ..self
+        }
+    }
+
+    pub fn span(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn metadata<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    #[track_caller]
+    pub fn build(self) -> Ereport {
+        Ereport {
+            stack: self.stack,
+            location: capture::location(),
+            backtrace: capture::backtrace(),
+            severity: self.severity,
+            code: self.code,
+            spans: self.spans,
+            metadata: self.metadata,
+            ..Ereport::bare(self.msg)
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::backtrace::Backtrace;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, OnceLock};
+
+static CAPTURE_LOCATIONS: AtomicBool = AtomicBool::new(true);
+
+const BACKTRACES_FROM_ENV: u8 = 0;
+const BACKTRACES_OFF: u8 = 1;
+const BACKTRACES_ON: u8 = 2;
+
+static CAPTURE_BACKTRACES: AtomicU8 = AtomicU8::new(BACKTRACES_FROM_ENV);
+
+/// Enables or disables capturing the source location of new reports, for the whole process.
+///
+/// Capturing is enabled by default. It is cheap, but not free, so it can be disabled around
+/// hot paths which create many reports.
+///
+/// # Example
+/// ```rust
+/// use erep::Ereport;
+///
+/// erep::set_capture_locations(false);
+/// assert!(Ereport::new("untracked").location().is_none());
+///
+/// erep::set_capture_locations(true);
+/// assert!(Ereport::new("tracked").location().is_some());
+/// ```
+pub fn set_capture_locations(enabled: bool) {
+    CAPTURE_LOCATIONS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the source location of new reports is captured, see `set_capture_locations`.
+pub fn captures_locations() -> bool {
+    CAPTURE_LOCATIONS.load(Ordering::Relaxed)
+}
+
+/// The location of the caller, if capturing locations is enabled.
+#[track_caller]
+pub(crate) fn location() -> Option<Location> {
+    let caller = std::panic::Location::caller();
+    captures_locations().then(|| Location::from(caller))
+}
+
+/// Enables or disables capturing a backtrace for new reports, for the whole process,
+/// overriding the environment.
+///
+/// By default backtraces are captured if the `EREP_BACKTRACE` environment variable is set to
+/// anything but `0`. If it is not set, `RUST_LIB_BACKTRACE` and then `RUST_BACKTRACE` are
+/// checked the same way, like `std::backtrace::Backtrace::capture` does.
+///
+/// # Example
+/// ```rust
+/// use erep::Ereport;
+///
+/// erep::set_capture_backtraces(true);
+/// assert!(Ereport::new("traced").backtrace().is_some());
+///
+/// erep::set_capture_backtraces(false);
+/// assert!(Ereport::new("untraced").backtrace().is_none());
+/// ```
+pub fn set_capture_backtraces(enabled: bool) {
+    let mode = if enabled {
+        BACKTRACES_ON
+    } else {
+        BACKTRACES_OFF
+    };
+    CAPTURE_BACKTRACES.store(mode, Ordering::Relaxed);
+}
+
+/// Whether a backtrace is captured for new reports, see `set_capture_backtraces`.
+pub fn captures_backtraces() -> bool {
+    static FROM_ENV: OnceLock<bool> = OnceLock::new();
+
+    match CAPTURE_BACKTRACES.load(Ordering::Relaxed) {
+        BACKTRACES_ON => true,
+        BACKTRACES_OFF => false,
+        _ => *FROM_ENV.get_or_init(|| {
+            ["EREP_BACKTRACE", "RUST_LIB_BACKTRACE", "RUST_BACKTRACE"]
+                .into_iter()
+                .find_map(std::env::var_os)
+                .is_some_and(|val| val != "0")
+        }),
+    }
+}
+
+/// A backtrace of the current thread, if capturing backtraces is enabled.
+pub(crate) fn backtrace() -> Option<Arc<Backtrace>> {
+    captures_backtraces().then(|| Arc::new(Backtrace::force_capture()))
+}
+
+/// The place in the source code where an `Ereport` was created.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    file: Cow<'static, str>,
+    line: u32,
+    column: u32,
+}
+
+impl Location {
+    pub fn new<S>(file: S, line: u32, column: u32) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            file: file.into(),
+            line,
+            column,
+        }
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl From<&'static std::panic::Location<'static>> for Location {
+    fn from(location: &'static std::panic::Location<'static>) -> Self {
+        Self::new(location.file(), location.line(), location.column())
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
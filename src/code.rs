@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+static REGISTRY: RwLock<BTreeMap<String, CodeInfo>> = RwLock::new(BTreeMap::new());
+
+/// A registered error code, with a short title and a long-form explanation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeInfo {
+    code: String,
+    title: String,
+    explanation: String,
+}
+
+impl CodeInfo {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn explanation(&self) -> &str {
+        &self.explanation
+    }
+}
+
+/// Registers an error code, like `E0042`, for the whole process, returning the previous
+/// registration of the same code, if any.
+///
+/// # Example
+/// ```rust
+/// use erep::Ereport;
+///
+/// erep::register_code(
+///     "E0042",
+///     "missing key",
+///     "A required key is missing from the config file.\n\nAdd the key, with a value.",
+/// );
+///
+/// let rep = Ereport::new("missing key `name`").with_code("E0042");
+/// assert_eq!(rep.to_string(), "error[E0042]: missing key `name`");
+///
+/// let info = erep::lookup_code(rep.code().unwrap()).unwrap();
+/// assert_eq!(info.title(), "missing key");
+///
+/// assert_eq!(
+///     erep::explain("E0042").unwrap(),
+///     "E0042: missing key\n\nA required key is missing from the config file.\n\nAdd the key, with a value."
+/// );
+/// assert_eq!(erep::explain("E9999"), None);
+/// ```
+pub fn register_code<C, T, E>(code: C, title: T, explanation: E) -> Option<CodeInfo>
+where
+    C: Into<String>,
+    T: Into<String>,
+    E: Into<String>,
+{
+    let info = CodeInfo {
+        code: code.into(),
+        title: title.into(),
+        explanation: explanation.into(),
+    };
+
+    REGISTRY
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(info.code.clone(), info)
+}
+
+/// Looks up a code registered with `register_code`.
+pub fn lookup_code(code: &str) -> Option<CodeInfo> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(code)
+        .cloned()
+}
+
+/// All codes registered with `register_code`, sorted by code.
+pub fn registered_codes() -> Vec<CodeInfo> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// The long-form explanation of a registered code, headed by the code and its title, as
+/// shown by a `--explain` flag.
+pub fn explain(code: &str) -> Option<String> {
+    lookup_code(code).map(|info| format!("{}: {}\n\n{}", info.code, info.title, info.explanation))
+}
@@ -0,0 +1,183 @@
+use std::sync::Mutex;
+
+use crate::{capture, Ereport, Location};
+
+/// The order in which `SharedCollector::into_ereport` puts the collected reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Order {
+    /// The order in which the reports were pushed, which depends on how the threads were
+    /// scheduled.
+    #[default]
+    Insertion,
+    /// Sorted by key, keeping the insertion order of reports with the same key, followed by
+    /// the reports pushed without a key. Deterministic as long as each thread uses its own
+    /// keys.
+    Key,
+}
+
+/// Collects reports from several threads.
+///
+/// # Example
+/// ```rust
+/// use erep::{Ereport, Order, SharedCollector};
+///
+/// let collector = SharedCollector::new().order(Order::Key);
+///
+/// std::thread::scope(|s| {
+///     for worker in ["b", "a", "c"] {
+///         let collector = &collector;
+///         s.spawn(move || {
+///             collector.push_keyed(worker, Ereport::new(format!("worker {worker} failed")));
+///         });
+///     }
+/// });
+///
+/// let rep = collector.into_ereport().unwrap();
+/// let messages: Vec<_> = rep.children().iter().map(|r| r.message()).collect();
+///
+/// assert_eq!(messages, ["worker a failed", "worker b failed", "worker c failed"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedCollector {
+    entries: Mutex<Vec<Entry>>,
+    order: Order,
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: Option<String>,
+    rep: Ereport,
+}
+
+impl SharedCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn order(self, order: Order) -> Self {
+        Self { order, ..self }
+    }
+
+    pub fn push(&self, rep: Ereport) {
+        self.insert(None, rep);
+    }
+
+    /// Pushes a report under a key, used to sort the reports with `Order::Key`.
+    pub fn push_keyed<K>(&self, key: K, rep: Ereport)
+    where
+        K: Into<String>,
+    {
+        self.insert(Some(key.into()), rep);
+    }
+
+    fn insert(&self, key: Option<String>, rep: Ereport) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Entry { key, rep });
+    }
+
+    /// Starts a scope for a single thread, which collects its reports without locking, and
+    /// pushes them as the children of one report with the given name, keyed by that name,
+    /// once it is dropped. Nothing is pushed if the scope recorded nothing. The report gets
+    /// the highest severity of its children, so a scope of warnings does not become an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Ereport, Order, SharedCollector};
+    ///
+    /// let collector = SharedCollector::new().order(Order::Key);
+    ///
+    /// std::thread::scope(|s| {
+    ///     for worker in 0..4 {
+    ///         let collector = &collector;
+    ///         s.spawn(move || {
+    ///             let mut scope = collector.scope(format!("worker {worker}"));
+    ///             for record in 0..3 {
+    ///                 if (worker + record) % 2 == 0 {
+    ///                     scope.push(Ereport::new(format!("record {record} is invalid")));
+    ///                 }
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// let rep = collector.into_ereport().unwrap();
+    ///
+    /// assert_eq!(rep.children().len(), 4);
+    /// assert_eq!(rep.children()[0].message(), "worker 0");
+    /// assert_eq!(rep.children()[0].children().len(), 2);
+    /// assert_eq!(rep.children()[1].children().len(), 1);
+    ///
+    /// let collector = SharedCollector::new();
+    /// collector.scope("worker 0").push(Ereport::warning("record 0 is empty"));
+    ///
+    /// assert!(!collector.into_ereport().unwrap().has_errors());
+    /// ```
+    #[track_caller]
+    pub fn scope<S>(&self, name: S) -> CollectorScope<'_>
+    where
+        S: Into<String>,
+    {
+        CollectorScope {
+            collector: self,
+            name: name.into(),
+            location: capture::location(),
+            reps: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All collected reports, in the configured order, or `None` if there are none.
+    #[track_caller]
+    pub fn into_ereport(self) -> Option<Ereport> {
+        let mut entries = self.entries.into_inner().unwrap_or_else(|e| e.into_inner());
+        if self.order == Order::Key {
+            entries.sort_by(|a, b| (a.key.is_none(), &a.key).cmp(&(b.key.is_none(), &b.key)));
+        }
+        Ereport::group(entries.into_iter().map(|e| e.rep).collect())
+    }
+}
+
+/// Collects the reports of a single thread, created by `SharedCollector::scope`.
+#[derive(Debug)]
+pub struct CollectorScope<'a> {
+    collector: &'a SharedCollector,
+    name: String,
+    location: Option<Location>,
+    reps: Vec<Ereport>,
+}
+
+impl CollectorScope<'_> {
+    pub fn push(&mut self, rep: Ereport) {
+        self.reps.push(rep);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reps.is_empty()
+    }
+}
+
+impl Drop for CollectorScope<'_> {
+    fn drop(&mut self) {
+        if self.reps.is_empty() {
+            return;
+        }
+
+        let name = std::mem::take(&mut self.name);
+        let rep = Ereport {
+            severity: Ereport::wrapping_severity(&self.reps),
+            stack: std::mem::take(&mut self.reps),
+            location: self.location.take(),
+            ..Ereport::bare(name.clone())
+        };
+        self.collector.push_keyed(name, rep);
+    }
+}
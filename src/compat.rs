@@ -0,0 +1,58 @@
+//! Conversions between `Erep`/`Ereport` and other error handling crates.
+
+mod anyhow;
+mod eyre;
+
+use std::error::Error;
+
+use crate::{capture, Ereport, Severity};
+
+pub use self::eyre::{install_eyre_hook, ErepHandler};
+
+/// Whether the report is nothing but a message, which can become a plain message or context
+/// layer without losing anything. Its children are not considered.
+fn is_plain_layer(rep: &Ereport) -> bool {
+    rep.severity == Severity::Error
+        && rep.code.is_none()
+        && rep.spans.is_empty()
+        && rep.metadata.is_empty()
+}
+
+/// Builds a report from an error chain, outermost error first, where every layer becomes the
+/// only child of the one before it. If the chain contains an `Ereport`, that report is used
+/// as is, and the rest of the chain, which is its own first branch, is skipped.
+#[track_caller]
+fn from_chain<'a, I>(chain: I) -> Ereport
+where
+    I: IntoIterator<Item = &'a (dyn Error + 'static)>,
+{
+    let location = capture::location();
+    let mut layers = Vec::new();
+    let mut root = None;
+
+    for cause in chain {
+        if let Some(rep) = cause.downcast_ref::<Ereport>() {
+            root = Some(rep.clone());
+            break;
+        }
+        layers.push(cause.to_string());
+    }
+
+    // The innermost layer is where the error came from, so it gets the backtrace.
+    let mut backtrace = if root.is_none() {
+        capture::backtrace()
+    } else {
+        None
+    };
+
+    let mut rep = root;
+    for msg in layers.into_iter().rev() {
+        rep = Some(Ereport {
+            location: location.clone(),
+            backtrace: backtrace.take(),
+            ..Ereport::bare(msg).push_opt(rep)
+        });
+    }
+
+    rep.unwrap_or_else(|| Ereport::bare(String::new()))
+}
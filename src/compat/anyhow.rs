@@ -0,0 +1,125 @@
+use crate::{Erep, Ereport};
+
+impl<T> Erep<T> {
+    /// Maps a function F, on value T, returning `anyhow::Result<U>`, turning it from
+    /// Erep<T> to Erep<Option<U>>.
+    ///
+    /// Works like `Erep::emap`, except that the whole `anyhow` context chain of the error is
+    /// kept, as nested `Ereport`s.
+    ///
+    /// # Example
+    /// ```rust
+    /// use anyhow::Context;
+    /// use erep::{Erep, Ereport};
+    ///
+    /// let port = Erep::with_report("80x", Ereport::warning("`PORT` is deprecated"))
+    ///     .amap(|s| s.parse::<u16>().context("invalid port"));
+    /// let (val, rep) = port.unwrap_with_err();
+    /// let rep = rep.unwrap();
+    ///
+    /// assert_eq!(val, None);
+    /// assert_eq!(rep.children()[0].message(), "`PORT` is deprecated");
+    ///
+    /// let err = &rep.children()[1];
+    /// assert_eq!(err.message(), "invalid port");
+    /// assert_eq!(err.children()[0].message(), "invalid digit found in string");
+    /// ```
+    #[track_caller]
+    pub fn amap<F, U>(self, f: F) -> Erep<Option<U>>
+    where
+        F: Fn(T) -> ::anyhow::Result<U>,
+    {
+        let (vo, ro) = self.unwrap_with_err();
+
+        match f(vo) {
+            Ok(v) => Erep {
+                val: Some(v),
+                rep: ro,
+            },
+            Err(e) => Erep {
+                val: None::<U>,
+                rep: Ereport::combine(ro, Some(Ereport::from(e))),
+            },
+        }
+    }
+}
+
+impl Ereport {
+    /// Turns the report into an `anyhow::Error`.
+    ///
+    /// Reports with a single child become context layers around that child, and leaves become
+    /// plain messages, as long as they have nothing but a message. Reports with several
+    /// children, or with a severity other than error, a code, spans or metadata, are kept
+    /// whole, as the root cause, so converting back with `Ereport::from` gives the same tree.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// let rep = Ereport::new("while loading config")
+    ///     .push(Ereport::new("while reading `erep.toml`").push(Ereport::new("file not found")));
+    ///
+    /// let err = rep.into_anyhow();
+    /// let chain: Vec<String> = err.chain().map(ToString::to_string).collect();
+    ///
+    /// assert_eq!(
+    ///     chain,
+    ///     ["while loading config", "while reading `erep.toml`", "file not found"]
+    /// );
+    ///
+    /// let rep = Ereport::new("while loading config")
+    ///     .push(Ereport::warning("deprecated key `port`").with_code("E0007"));
+    /// let back = Ereport::from(rep.clone().into_anyhow());
+    ///
+    /// assert_eq!(back, rep);
+    /// assert_eq!(back.children()[0].code(), Some("E0007"));
+    /// ```
+    pub fn into_anyhow(self) -> ::anyhow::Error {
+        if !super::is_plain_layer(&self) {
+            return ::anyhow::Error::new(self);
+        }
+
+        if self.stack.is_empty() {
+            return ::anyhow::Error::msg(self.msg);
+        }
+
+        if self.is_group() || self.entries().len() > 1 {
+            return ::anyhow::Error::new(self);
+        }
+
+        let msg = self.msg;
+        match self
+            .stack
+            .into_iter()
+            .flat_map(Ereport::into_entries)
+            .next()
+        {
+            Some(child) => child.into_anyhow().context(msg),
+            None => ::anyhow::Error::msg(msg),
+        }
+    }
+}
+
+/// Keeps every layer of the `anyhow` context chain, outermost first, each as the only child
+/// of the one before it. If the chain contains an `Ereport`, for example one created by
+/// `Ereport::into_anyhow`, that report is used as is.
+///
+/// # Example
+/// ```rust
+/// use anyhow::Context;
+/// use erep::Ereport;
+///
+/// let err = "x".parse::<i32>().context("invalid port").unwrap_err();
+/// let rep = Ereport::from(err);
+///
+/// assert_eq!(
+///     rep.to_string(),
+///     "invalid port\n└── [1] invalid digit found in string"
+/// );
+/// ```
+impl From<::anyhow::Error> for Ereport {
+    #[track_caller]
+    fn from(err: ::anyhow::Error) -> Self {
+        super::from_chain(err.chain())
+    }
+}
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{ColorChoice, Ereport, Renderer, Stream};
+
+impl Ereport {
+    /// Turns the report into an `eyre::Report`.
+    ///
+    /// Reports with a single child become `wrap_err` layers around that child, and leaves
+    /// become plain messages, as long as they have nothing but a message. Reports with several
+    /// children, or with a severity other than error, a code, spans or metadata, are kept
+    /// whole, as the root cause, so converting back with `Ereport::from` gives the same tree.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// let rep = Ereport::new("while loading config").push(Ereport::new("file not found"));
+    ///
+    /// let report = rep.into_eyre();
+    /// let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    ///
+    /// assert_eq!(chain, ["while loading config", "file not found"]);
+    ///
+    /// let rep = Ereport::warning("deprecated key `port`").with_code("E0007");
+    /// let back = Ereport::from(rep.clone().into_eyre());
+    ///
+    /// assert_eq!(back, rep);
+    /// assert_eq!(back.severity(), erep::Severity::Warning);
+    /// ```
+    pub fn into_eyre(self) -> ::eyre::Report {
+        if !super::is_plain_layer(&self) {
+            return ::eyre::Report::new(self);
+        }
+
+        if self.stack.is_empty() {
+            return ::eyre::Report::msg(self.msg);
+        }
+
+        if self.is_group() || self.entries().len() > 1 {
+            return ::eyre::Report::new(self);
+        }
+
+        let msg = self.msg;
+        match self
+            .stack
+            .into_iter()
+            .flat_map(Ereport::into_entries)
+            .next()
+        {
+            Some(child) => child.into_eyre().wrap_err(msg),
+            None => ::eyre::Report::msg(msg),
+        }
+    }
+}
+
+/// Keeps every layer of the `eyre` chain, outermost first, each as the only child of the one
+/// before it. If the chain contains an `Ereport`, for example one created by
+/// `Ereport::into_eyre`, that report is used as is.
+///
+/// # Example
+/// ```rust
+/// use erep::Ereport;
+/// use eyre::WrapErr;
+///
+/// let report = "x".parse::<i32>().wrap_err("invalid port").unwrap_err();
+/// let rep = Ereport::from(report);
+///
+/// assert_eq!(
+///     rep.to_string(),
+///     "invalid port\n└── [1] invalid digit found in string"
+/// );
+/// ```
+impl From<::eyre::Report> for Ereport {
+    #[track_caller]
+    fn from(report: ::eyre::Report) -> Self {
+        super::from_chain(report.chain())
+    }
+}
+
+/// An `eyre::EyreHandler` which renders the `Debug` output of an `eyre::Report` as an
+/// `Ereport` tree, so every accumulated error of an embedded `Ereport` is shown, instead of
+/// just the first branch of the chain.
+///
+/// # Example
+/// ```rust
+/// use erep::{ErepHandler, Ereport};
+///
+/// eyre::set_hook(Box::new(|_| Box::new(ErepHandler::default()))).unwrap();
+///
+/// let rep = Ereport::new("invalid config")
+///     .push(Ereport::new("missing key `name`"))
+///     .push(Ereport::new("invalid port"));
+///
+/// let report = eyre::Report::from(rep).wrap_err("failed to start");
+///
+/// assert_eq!(
+///     format!("{report:?}"),
+///     "failed to start\n\
+///      └── [1] invalid config\n    \
+///          ├── [1.1] missing key `name`\n    \
+///          └── [1.2] invalid port"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ErepHandler {
+    renderer: Renderer,
+}
+
+impl ErepHandler {
+    /// Creates a handler which renders reports with the given renderer.
+    pub fn new(renderer: Renderer) -> Self {
+        Self { renderer }
+    }
+}
+
+impl ::eyre::EyreHandler for ErepHandler {
+    fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rep = super::from_chain(std::iter::successors(Some(error), |e| (*e).source()));
+        fmt::Display::fmt(&self.renderer.render(&rep), f)
+    }
+}
+
+/// Installs an `ErepHandler` as the global `eyre` hook, with the default renderer, using
+/// colors if stderr allows it, see `ColorChoice::Auto`.
+///
+/// Fails if a hook has already been installed, which also happens implicitly when the first
+/// `eyre::Report` is created.
+pub fn install_eyre_hook() -> Result<(), ::eyre::InstallError> {
+    let renderer = Renderer::new().auto_color(ColorChoice::Auto, Stream::Stderr);
+    ::eyre::set_hook(Box::new(move |_| {
+        Box::new(ErepHandler::new(renderer.clone()))
+    }))
+}
@@ -0,0 +1,104 @@
+use crate::{Erep, Ereport, NONE_MSG};
+
+/// Lifts a `Result` into an `Erep`, so it can flow into the rest of an erep pipeline.
+///
+/// # Example
+/// ```rust
+/// use erep::ResultExt;
+///
+/// let port = "x".parse::<u16>().into_erep(8080);
+/// let (val, rep) = port.unwrap_with_err();
+///
+/// assert_eq!(val, 8080);
+/// assert_eq!(rep.unwrap().to_string(), "ParseIntError { kind: InvalidDigit }");
+///
+/// let port = "x".parse::<u16>().into_erep_with("invalid port");
+/// let (val, rep) = port.unwrap_with_err();
+///
+/// assert_eq!(val, None);
+/// assert_eq!(rep.unwrap().to_string(), "invalid port");
+/// ```
+pub trait ResultExt<T, E> {
+    /// Keeps the value on success, otherwise uses the default, and reports the error, using
+    /// its `Debug` output, like `Erep::emap`.
+    fn into_erep(self, default: T) -> Erep<T>;
+
+    /// Keeps the value on success, otherwise reports the error with the given message.
+    fn into_erep_with<S>(self, msg: S) -> Erep<Option<T>>
+    where
+        S: Into<String>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E>
+where
+    E: std::fmt::Debug,
+{
+    #[track_caller]
+    fn into_erep(self, default: T) -> Erep<T> {
+        match self {
+            Ok(val) => Erep::ok(val),
+            Err(e) => Erep::with_report(default, Ereport::new(format!("{e:?}"))),
+        }
+    }
+
+    #[track_caller]
+    fn into_erep_with<S>(self, msg: S) -> Erep<Option<T>>
+    where
+        S: Into<String>,
+    {
+        match self {
+            Ok(val) => Erep::ok(Some(val)),
+            Err(_) => Erep::with_report(None, Ereport::new(msg)),
+        }
+    }
+}
+
+/// Lifts an `Option` into an `Erep`, so it can flow into the rest of an erep pipeline.
+///
+/// # Example
+/// ```rust
+/// use erep::OptionExt;
+///
+/// let name = None::<&str>.into_erep("erep");
+/// let (val, rep) = name.unwrap_with_err();
+///
+/// assert_eq!(val, "erep");
+/// assert_eq!(rep.unwrap().to_string(), "Got None, instead of Some");
+///
+/// let name = Some("erep").into_erep_with("missing key `name`");
+/// let (val, rep) = name.unwrap_with_err();
+///
+/// assert_eq!(val, Some("erep"));
+/// assert!(rep.is_none());
+/// ```
+pub trait OptionExt<T> {
+    /// Keeps the value if there is one, otherwise uses the default, and reports it, like
+    /// `Erep::omap`.
+    fn into_erep(self, default: T) -> Erep<T>;
+
+    /// Keeps the value if there is one, otherwise reports it with the given message.
+    fn into_erep_with<S>(self, msg: S) -> Erep<Option<T>>
+    where
+        S: Into<String>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[track_caller]
+    fn into_erep(self, default: T) -> Erep<T> {
+        match self {
+            Some(val) => Erep::ok(val),
+            None => Erep::with_report(default, Ereport::new(NONE_MSG)),
+        }
+    }
+
+    #[track_caller]
+    fn into_erep_with<S>(self, msg: S) -> Erep<Option<T>>
+    where
+        S: Into<String>,
+    {
+        match self {
+            Some(val) => Erep::ok(Some(val)),
+            None => Erep::with_report(None, Ereport::new(msg)),
+        }
+    }
+}
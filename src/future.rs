@@ -0,0 +1,289 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{capture, Erep, Ereport, NONE_MSG};
+
+// `#[track_caller]` does not reach into futures, so each combinator captures the location
+// of its caller up front, and moves it into the future.
+
+impl<T> Erep<T> {
+    /// Like `Erep::map`, for a function F returning a future.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::future::Future;
+    /// use std::pin::pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// use erep::{Erep, Ereport};
+    ///
+    /// fn block_on<F: Future>(f: F) -> F::Output {
+    ///     let mut f = pin!(f);
+    ///     let mut cx = Context::from_waker(Waker::noop());
+    ///     loop {
+    ///         if let Poll::Ready(val) = f.as_mut().poll(&mut cx) {
+    ///             return val;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let port = Erep::with_report(80, Ereport::warning("using the default port"))
+    ///     .map_async(|p| async move { Erep::ok(p + 8000) });
+    /// let (val, rep) = block_on(port).unwrap_with_err();
+    ///
+    /// assert_eq!(val, 8080);
+    /// assert_eq!(rep.unwrap().to_string(), "warning: using the default port");
+    /// ```
+    #[track_caller]
+    pub fn map_async<F, Fut, U>(self, f: F) -> impl Future<Output = Erep<U>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Erep<U>>,
+    {
+        let location = capture::location();
+        async move {
+            let (vo, ro) = self.unwrap_with_err();
+            let (v, r) = f(vo).await.unwrap_with_err();
+            Erep {
+                val: v,
+                rep: Ereport::group_at(ro.into_iter().chain(r).collect(), location),
+            }
+        }
+    }
+
+    /// Like `Erep::emap`, for a function F returning a future.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::future::Future;
+    /// use std::pin::pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// use erep::{Erep, Ereport};
+    ///
+    /// fn block_on<F: Future>(f: F) -> F::Output {
+    ///     let mut f = pin!(f);
+    ///     let mut cx = Context::from_waker(Waker::noop());
+    ///     loop {
+    ///         if let Poll::Ready(val) = f.as_mut().poll(&mut cx) {
+    ///             return val;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let port = Erep::with_report("80x", Ereport::warning("`PORT` is deprecated"))
+    ///     .emap_async(|s| async move { s.parse::<u16>() }, Some("invalid port".into()));
+    /// let (val, rep) = block_on(port).unwrap_with_err();
+    /// let rep = rep.unwrap();
+    ///
+    /// assert_eq!(val, None);
+    /// assert_eq!(rep.children()[0].message(), "`PORT` is deprecated");
+    /// assert_eq!(rep.children()[1].message(), "invalid port");
+    ///
+    /// let port = Erep::ok("80").emap_async(|s| async move { s.parse::<u16>() }, None);
+    /// assert_eq!(block_on(port).unwrap_with_err(), (Some(80), None));
+    /// ```
+    #[track_caller]
+    pub fn emap_async<F, Fut, U, E>(
+        self,
+        f: F,
+        msg: Option<String>,
+    ) -> impl Future<Output = Erep<Option<U>>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<U, E>>,
+        E: Debug,
+    {
+        let location = capture::location();
+        async move {
+            let (vo, ro) = self.unwrap_with_err();
+            match f(vo).await {
+                Ok(v) => Erep {
+                    val: Some(v),
+                    rep: ro,
+                },
+                Err(e) => {
+                    let msg = msg.unwrap_or_else(|| format!("{e:?}"));
+                    Erep {
+                        val: None,
+                        rep: Ereport::failure(ro, msg, None, location),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Like `Erep::omap`, for a function F returning a future.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::future::Future;
+    /// use std::pin::pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// use erep::{Erep, Ereport};
+    ///
+    /// fn block_on<F: Future>(f: F) -> F::Output {
+    ///     let mut f = pin!(f);
+    ///     let mut cx = Context::from_waker(Waker::noop());
+    ///     loop {
+    ///         if let Poll::Ready(val) = f.as_mut().poll(&mut cx) {
+    ///             return val;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let name = Erep::ok(None::<&str>).omap_async(|n| async move { n }, None);
+    /// let (val, rep) = block_on(name).unwrap_with_err();
+    ///
+    /// assert_eq!(val, None);
+    /// assert_eq!(rep.unwrap().to_string(), "Got None, instead of Some");
+    ///
+    /// let name = Erep::ok(Some("erep")).omap_async(|n| async move { n }, None);
+    /// assert_eq!(block_on(name).unwrap_with_err(), (Some("erep"), None));
+    /// ```
+    #[track_caller]
+    pub fn omap_async<F, Fut, U>(
+        self,
+        f: F,
+        msg: Option<String>,
+    ) -> impl Future<Output = Erep<Option<U>>>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Option<U>>,
+    {
+        let location = capture::location();
+        async move {
+            let (vo, ro) = self.unwrap_with_err();
+            match f(vo).await {
+                Some(v) => Erep {
+                    val: Some(v),
+                    rep: ro,
+                },
+                None => {
+                    let msg = msg.unwrap_or_else(|| NONE_MSG.to_string());
+                    Erep {
+                        val: None,
+                        rep: Ereport::failure(ro, msg, None, location),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Drives all futures concurrently, and collects their values in order, keeping the reports
+/// of all of them, like collecting an iterator of `Erep`s.
+///
+/// Does not depend on a runtime, every future is polled whenever the returned future is.
+///
+/// # Example
+/// ```rust
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// use erep::{join_all, Erep, Ereport};
+///
+/// fn block_on<F: Future>(f: F) -> F::Output {
+///     let mut f = pin!(f);
+///     let mut cx = Context::from_waker(Waker::noop());
+///     loop {
+///         if let Poll::Ready(val) = f.as_mut().poll(&mut cx) {
+///             return val;
+///         }
+///     }
+/// }
+///
+/// async fn lookup(name: &str) -> Erep<u16> {
+///     match name {
+///         "http" => Erep::ok(80),
+///         "https" => Erep::ok(443),
+///         _ => Erep::with_report(0, Ereport::new(format!("unknown service `{name}`"))),
+///     }
+/// }
+///
+/// let ports = join_all(["http", "gopher", "https"].map(lookup));
+/// let (val, rep) = block_on(ports).unwrap_with_err();
+///
+/// assert_eq!(val, vec![80, 0, 443]);
+/// assert_eq!(rep.unwrap().to_string(), "unknown service `gopher`");
+/// ```
+#[track_caller]
+pub fn join_all<I>(futures: I) -> JoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    JoinAll {
+        slots: futures
+            .into_iter()
+            .map(|f| Slot::Pending(Box::pin(f)))
+            .collect(),
+        location: capture::location(),
+    }
+}
+
+/// The future returned by `join_all`.
+#[must_use = "futures do nothing unless polled"]
+pub struct JoinAll<F>
+where
+    F: Future,
+{
+    slots: Vec<Slot<F>>,
+    location: Option<crate::Location>,
+}
+
+enum Slot<F>
+where
+    F: Future,
+{
+    Pending(Pin<Box<F>>),
+    Done(F::Output),
+    Taken,
+}
+
+// The futures are boxed, and never pinned in place.
+impl<F> Unpin for JoinAll<F> where F: Future {}
+
+impl<F, T> Future for JoinAll<F>
+where
+    F: Future<Output = Erep<T>>,
+{
+    type Output = Erep<Vec<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut pending = false;
+        for slot in &mut self.slots {
+            if let Slot::Pending(f) = slot {
+                match f.as_mut().poll(cx) {
+                    Poll::Ready(val) => *slot = Slot::Done(val),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            return Poll::Pending;
+        }
+
+        let mut vals = Vec::with_capacity(self.slots.len());
+        let mut reps = Vec::new();
+        for slot in &mut self.slots {
+            match std::mem::replace(slot, Slot::Taken) {
+                Slot::Done(erep) => {
+                    let (val, rep) = erep.unwrap_with_err();
+                    vals.push(val);
+                    reps.extend(rep);
+                }
+                _ => panic!("`JoinAll` polled after completion"),
+            }
+        }
+
+        Poll::Ready(Erep {
+            val: vals,
+            rep: Ereport::group_at(reps, self.location.clone()),
+        })
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Erep, Ereport, NONE_MSG};
+
+/// Collects the values into any collection, and merges every report, in order, like
+/// `Ereport::merge`. The collected `Erep` only has a report if one of the values had one, and
+/// a single report is kept as is. The entries of a grouping report, like one from an earlier
+/// collect, take its place, so the merged report does not tell which value a report came
+/// from. Put that in the reports themselves, or use `Erep::context`, if it matters.
+///
+/// Any collection which can be collected from the values works, like `Vec<T>`, or
+/// `HashMap<K, V>` from values of `(K, V)`.
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use erep::{Erep, Ereport};
+///
+/// let ports: Erep<Vec<u16>> = vec![
+///     Erep::ok(80),
+///     Erep::with_report(0, Ereport::new("invalid port `x`")),
+///     Erep::with_report(443, Ereport::warning("port 443 needs root")),
+/// ]
+/// .into_iter()
+/// .collect();
+/// let (val, rep) = ports.unwrap_with_err();
+/// let rep = rep.unwrap();
+///
+/// assert_eq!(val, [80, 0, 443]);
+/// assert_eq!(rep.children().len(), 2);
+/// assert_eq!(rep.children()[0].message(), "invalid port `x`");
+/// assert_eq!(rep.children()[1].message(), "port 443 needs root");
+///
+/// let services: Erep<HashMap<&str, u16>> = [
+///     Erep::ok(("http", 80)),
+///     Erep::with_report(("gopher", 70), Ereport::warning("`gopher` is deprecated")),
+/// ]
+/// .into_iter()
+/// .collect();
+/// let (val, rep) = services.unwrap_with_err();
+///
+/// assert_eq!(val["gopher"], 70);
+/// assert_eq!(rep.unwrap().to_string(), "warning: `gopher` is deprecated");
+///
+/// let clean: Erep<Vec<u16>> = vec![Erep::ok(1), Erep::ok(2)].into_iter().collect();
+/// assert!(clean.is_clean());
+/// ```
+impl<T, C> FromIterator<Erep<T>> for Erep<C>
+where
+    C: FromIterator<T>,
+{
+    #[track_caller]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Erep<T>>,
+    {
+        let mut reps = Vec::new();
+        let val = iter
+            .into_iter()
+            .map(|erep| {
+                let (val, rep) = erep.unwrap_with_err();
+                reps.extend(rep);
+                val
+            })
+            .collect();
+
+        Erep {
+            val,
+            rep: Ereport::group(reps),
+        }
+    }
+}
+
+impl<U> Erep<Vec<U>> {
+    /// Runs F on every element, keeping the values of the ones that succeed, and recording
+    /// a child report for every one that fails, with the index of the element. Like
+    /// `Erep::emap`, the report uses the `Debug` output of the error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Erep;
+    ///
+    /// let parsed = Erep::traverse_results(["1", "x", "3", ""], |s| s.parse::<i32>());
+    /// let (vals, rep) = parsed.unwrap_with_err();
+    ///
+    /// assert_eq!(vals, [1, 3]);
+    /// assert_eq!(
+    ///     rep.unwrap().to_string(),
+    ///     "[1] index 1: ParseIntError { kind: InvalidDigit }\n\
+    ///      [2] index 3: ParseIntError { kind: Empty }"
+    /// );
+    /// ```
+    #[track_caller]
+    pub fn traverse_results<I, F, E>(iter: I, mut f: F) -> Self
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Result<U, E>,
+        E: std::fmt::Debug,
+    {
+        let mut vals = Vec::new();
+        let mut failures = Vec::new();
+
+        for (i, item) in iter.into_iter().enumerate() {
+            match f(item) {
+                Ok(v) => vals.push(v),
+                Err(e) => failures.push((i, format!("{e:?}"))),
+            }
+        }
+
+        Self::with_failures(vals, failures)
+    }
+
+    /// Runs F on every element, keeping the values of the ones that return `Some`, and
+    /// recording a child report for every one that returns `None`, with the index of the
+    /// element, and the given message, like `Erep::omap`.
+    #[track_caller]
+    pub fn traverse_options<I, F>(iter: I, mut f: F, msg: Option<String>) -> Self
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Option<U>,
+    {
+        let msg = msg.unwrap_or_else(|| String::from(NONE_MSG));
+        let mut vals = Vec::new();
+        let mut failures = Vec::new();
+
+        for (i, item) in iter.into_iter().enumerate() {
+            match f(item) {
+                Some(v) => vals.push(v),
+                None => failures.push((i, msg.clone())),
+            }
+        }
+
+        Self::with_failures(vals, failures)
+    }
+
+    /// Turns results into an `Erep` of the successful values, recording a child report for
+    /// every error, like `Erep::traverse_results`.
+    #[track_caller]
+    pub fn sequence<I, E>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Result<U, E>>,
+        E: std::fmt::Debug,
+    {
+        Self::traverse_results(iter, |r| r)
+    }
+
+    #[track_caller]
+    fn with_failures(vals: Vec<U>, failures: Vec<(usize, String)>) -> Self {
+        let mut reps = Vec::new();
+        for (i, msg) in failures {
+            reps.push(Ereport::new(format!("index {i}: {msg}")));
+        }
+
+        Erep {
+            val: vals,
+            rep: Ereport::group(reps),
+        }
+    }
+}
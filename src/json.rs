@@ -0,0 +1,792 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::{Ereport, Location, Severity, Span};
+
+/// The version of the JSON schema written by `Ereport::write_json`.
+///
+/// A document is an object holding the schema version and the report,
+/// `{ "version": 1, "report": Report }`, where a report is
+/// `{ "msg": String, "stack": [Report] }`, followed by these optional fields, which are left
+/// out when they are not set:
+/// - `location`, `{ "file": String, "line": Number, "column": Number }`
+/// - `severity`, one of `"help"`, `"note"`, `"warning"` or `"error"`, left out for errors
+/// - `code`, an error code like `"E0042"`
+/// - `spans`, `[{ "source": String, "start": Number, "end": Number, "label": String }]`, where
+///   the label is optional
+/// - `metadata`, `{ String: String }`
+/// - `group`, `true` for a report which only groups its children
+///
+/// Backtraces are not included. Unknown fields are ignored when parsing, so fields added to
+/// the schema later on are still readable by older versions.
+pub const JSON_VERSION: u64 = 1;
+
+/// The error returned when `Ereport::from_json` cannot parse a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    msg: String,
+    offset: Option<usize>,
+}
+
+impl JsonError {
+    fn new<S>(msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            msg: msg.into(),
+            offset: None,
+        }
+    }
+
+    fn at<S>(msg: S, offset: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            msg: msg.into(),
+            offset: Some(offset),
+        }
+    }
+
+    /// The byte offset into the input where the error was found, if it is a syntax error.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{} at byte {offset}", self.msg),
+            None => f.write_str(&self.msg),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+impl Ereport {
+    /// Serializes the report as a compact JSON document.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// // Leave out the locations, to keep the output short.
+    /// erep::set_capture_locations(false);
+    ///
+    /// let rep = Ereport::new("invalid config").push(Ereport::new("missing key \"name\""));
+    ///
+    /// assert_eq!(
+    ///     rep.to_json(),
+    ///     r#"{"version":1,"report":{"msg":"invalid config","stack":[{"msg":"missing key \"name\"","stack":[]}]}}"#
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    /// If the report is nested too deeply, see `Ereport::write_json`.
+    pub fn to_json(&self) -> String {
+        let mut out = Vec::new();
+        self.write_json(&mut out)
+            .expect("the report is nested too deeply to be written as JSON");
+        String::from_utf8(out).expect("the JSON emitter only writes UTF-8")
+    }
+
+    /// Serializes the report as an indented JSON document.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// // Leave out the locations, to keep the output short.
+    /// erep::set_capture_locations(false);
+    ///
+    /// let rep = Ereport::new("invalid config").push(Ereport::new("missing key"));
+    ///
+    /// assert_eq!(
+    ///     rep.to_json_pretty(),
+    ///     r#"{
+    ///   "version": 1,
+    ///   "report": {
+    ///     "msg": "invalid config",
+    ///     "stack": [
+    ///       {
+    ///         "msg": "missing key",
+    ///         "stack": []
+    ///       }
+    ///     ]
+    ///   }
+    /// }"#
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    /// If the report is nested too deeply, see `Ereport::write_json`.
+    pub fn to_json_pretty(&self) -> String {
+        let mut out = Vec::new();
+        self.write_json_pretty(&mut out)
+            .expect("the report is nested too deeply to be written as JSON");
+        String::from_utf8(out).expect("the JSON emitter only writes UTF-8")
+    }
+
+    /// Writes the report as a compact JSON document.
+    ///
+    /// Reports nested more than 128 levels deep, counting the report itself, fail with
+    /// `io::ErrorKind::InvalidInput`, as `Ereport::from_json` would not read them back.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Ereport, Span};
+    ///
+    /// let mut rep = Ereport::new("file not found").with_span(Span::new("erep.toml", 0..4));
+    /// for i in 1..128 {
+    ///     rep = rep.context(format!("layer {i}"));
+    /// }
+    ///
+    /// let back = Ereport::from_json(&rep.to_json()).unwrap();
+    /// assert_eq!(back, rep);
+    ///
+    /// let rep = rep.context("one too many");
+    /// let err = rep.write_json(&mut Vec::new()).unwrap_err();
+    /// assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    /// ```
+    pub fn write_json<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        Emitter::new(w, false).document(self)
+    }
+
+    /// Writes the report as an indented JSON document, see `Ereport::write_json`.
+    pub fn write_json_pretty<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        Emitter::new(w, true).document(self)
+    }
+
+    /// Parses a JSON document written by `Ereport::write_json`.
+    ///
+    /// Like `Ereport::write_json`, reports nested more than 128 levels deep are rejected, and
+    /// so are other objects and arrays nested deeper than such a report would be, so
+    /// malformed input cannot overflow the stack.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// let rep = Ereport::new("invalid config").push(Ereport::new("tab\there, ünïcode"));
+    /// let back = Ereport::from_json(&rep.to_json_pretty()).unwrap();
+    ///
+    /// assert_eq!(back.to_json(), rep.to_json());
+    ///
+    /// let err = Ereport::from_json(r#"{"version": 2, "report": {"msg": ""}}"#).unwrap_err();
+    /// assert_eq!(err.to_string(), "unsupported schema version 2, expected 1");
+    ///
+    /// let err = Ereport::from_json(&"[".repeat(200_000)).unwrap_err();
+    /// assert_eq!(err.to_string(), "nesting too deep at byte 258");
+    /// ```
+    pub fn from_json(input: &str) -> Result<Ereport, JsonError> {
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.document()?;
+
+        let version = match value.get("version") {
+            Some(Value::Number(n)) => *n,
+            Some(_) => return Err(JsonError::new("`version` must be a number")),
+            None => return Err(JsonError::new("missing field `version`")),
+        };
+        if version != JSON_VERSION as f64 {
+            return Err(JsonError::new(format!(
+                "unsupported schema version {version}, expected {JSON_VERSION}"
+            )));
+        }
+
+        match value.get("report") {
+            Some(report) => report_from_value(report),
+            None => Err(JsonError::new("missing field `report`")),
+        }
+    }
+}
+
+fn report_from_value(value: &Value) -> Result<Ereport, JsonError> {
+    if !matches!(value, Value::Object(_)) {
+        return Err(JsonError::new("a report must be an object"));
+    }
+
+    let msg = match value.get("msg") {
+        Some(Value::String(s)) => s.clone(),
+        Some(_) => return Err(JsonError::new("`msg` must be a string")),
+        None => return Err(JsonError::new("missing field `msg`")),
+    };
+
+    let stack = match value.get("stack") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(report_from_value)
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(JsonError::new("`stack` must be an array")),
+        None => Vec::new(),
+    };
+
+    let location = match value.get("location") {
+        Some(Value::Object(_)) => Some(location_from_value(value.get("location").unwrap())?),
+        Some(Value::Null) | None => None,
+        Some(_) => return Err(JsonError::new("`location` must be an object")),
+    };
+
+    let severity = match value.get("severity") {
+        Some(Value::String(s)) => Severity::from_name(s)
+            .ok_or_else(|| JsonError::new(format!("unknown severity `{s}`")))?,
+        Some(Value::Null) | None => Severity::Error,
+        Some(_) => return Err(JsonError::new("`severity` must be a string")),
+    };
+
+    let code = match value.get("code") {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Null) | None => None,
+        Some(_) => return Err(JsonError::new("`code` must be a string")),
+    };
+
+    let spans = match value.get("spans") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(span_from_value)
+            .collect::<Result<_, _>>()?,
+        Some(Value::Null) | None => Vec::new(),
+        Some(_) => return Err(JsonError::new("`spans` must be an array")),
+    };
+
+    let mut metadata = BTreeMap::new();
+    match value.get("metadata") {
+        Some(Value::Object(fields)) => {
+            for (key, value) in fields {
+                match value {
+                    Value::String(s) => metadata.insert(key.clone(), s.clone()),
+                    _ => return Err(JsonError::new("`metadata` values must be strings")),
+                };
+            }
+        }
+        Some(Value::Null) | None => {}
+        Some(_) => return Err(JsonError::new("`metadata` must be an object")),
+    }
+
+    let group = match value.get("group") {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Null) | None => false,
+        Some(_) => return Err(JsonError::new("`group` must be a boolean")),
+    };
+
+    Ok(Ereport {
+        stack,
+        location,
+        severity,
+        code,
+        spans,
+        metadata,
+        group,
+        ..Ereport::bare(msg)
+    })
+}
+
+fn span_from_value(value: &Value) -> Result<Span, JsonError> {
+    let source = match value.get("source") {
+        Some(Value::String(s)) => s.clone(),
+        _ => return Err(JsonError::new("`span.source` must be a string")),
+    };
+
+    let offset = |key: &str| match value.get(key) {
+        Some(Value::Number(n)) if n.fract() == 0.0 && *n >= 0.0 => Ok(*n as usize),
+        _ => Err(JsonError::new(format!("`span.{key}` must be an integer"))),
+    };
+
+    let span = Span::new(source, offset("start")?..offset("end")?);
+    match value.get("label") {
+        Some(Value::String(label)) => Ok(span.with_label(label.clone())),
+        Some(Value::Null) | None => Ok(span),
+        Some(_) => Err(JsonError::new("`span.label` must be a string")),
+    }
+}
+
+fn location_from_value(value: &Value) -> Result<Location, JsonError> {
+    let file = match value.get("file") {
+        Some(Value::String(s)) => s.clone(),
+        _ => return Err(JsonError::new("`location.file` must be a string")),
+    };
+
+    let number = |key: &str| match value.get(key) {
+        Some(Value::Number(n)) if n.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(n) => {
+            Ok(*n as u32)
+        }
+        _ => Err(JsonError::new(format!(
+            "`location.{key}` must be an integer"
+        ))),
+    };
+
+    Ok(Location::new(file, number("line")?, number("column")?))
+}
+
+/// How deeply reports may be nested in a document, counting the outermost one.
+const MAX_REPORT_DEPTH: usize = 128;
+
+struct Emitter<'w, W> {
+    w: &'w mut W,
+    pretty: bool,
+    depth: usize,
+    level: usize,
+}
+
+impl<'w, W> Emitter<'w, W>
+where
+    W: Write,
+{
+    fn new(w: &'w mut W, pretty: bool) -> Self {
+        Self {
+            w,
+            pretty,
+            depth: 0,
+            level: 0,
+        }
+    }
+
+    fn document(&mut self, rep: &Ereport) -> io::Result<()> {
+        self.open(b'{')?;
+        self.key(true, "version")?;
+        write!(self.w, "{JSON_VERSION}")?;
+        self.key(false, "report")?;
+        self.report(rep)?;
+        self.close(b'}', false)
+    }
+
+    fn report(&mut self, rep: &Ereport) -> io::Result<()> {
+        if self.level == MAX_REPORT_DEPTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("reports nested more than {MAX_REPORT_DEPTH} levels deep"),
+            ));
+        }
+
+        self.level += 1;
+        let result = self.fields(rep);
+        self.level -= 1;
+        result
+    }
+
+    fn fields(&mut self, rep: &Ereport) -> io::Result<()> {
+        self.open(b'{')?;
+        self.key(true, "msg")?;
+        self.string(&rep.msg)?;
+        self.key(false, "stack")?;
+        self.open(b'[')?;
+        for (i, child) in rep.stack.iter().enumerate() {
+            self.item(i == 0)?;
+            self.report(child)?;
+        }
+        self.close(b']', rep.stack.is_empty())?;
+
+        if let Some(location) = &rep.location {
+            self.key(false, "location")?;
+            self.open(b'{')?;
+            self.key(true, "file")?;
+            self.string(location.file())?;
+            self.key(false, "line")?;
+            write!(self.w, "{}", location.line())?;
+            self.key(false, "column")?;
+            write!(self.w, "{}", location.column())?;
+            self.close(b'}', false)?;
+        }
+
+        if !rep.severity.is_error() {
+            self.key(false, "severity")?;
+            self.string(rep.severity.as_str())?;
+        }
+
+        if let Some(code) = &rep.code {
+            self.key(false, "code")?;
+            self.string(code)?;
+        }
+
+        if !rep.spans.is_empty() {
+            self.key(false, "spans")?;
+            self.open(b'[')?;
+            for (i, span) in rep.spans.iter().enumerate() {
+                self.item(i == 0)?;
+                self.span(span)?;
+            }
+            self.close(b']', false)?;
+        }
+
+        if !rep.metadata.is_empty() {
+            self.key(false, "metadata")?;
+            self.open(b'{')?;
+            for (i, (key, value)) in rep.metadata.iter().enumerate() {
+                self.key(i == 0, key)?;
+                self.string(value)?;
+            }
+            self.close(b'}', false)?;
+        }
+
+        if rep.group {
+            self.key(false, "group")?;
+            self.w.write_all(b"true")?;
+        }
+
+        self.close(b'}', false)
+    }
+
+    fn span(&mut self, span: &Span) -> io::Result<()> {
+        self.open(b'{')?;
+        self.key(true, "source")?;
+        self.string(span.source())?;
+        self.key(false, "start")?;
+        write!(self.w, "{}", span.range().start)?;
+        self.key(false, "end")?;
+        write!(self.w, "{}", span.range().end)?;
+        if let Some(label) = span.label() {
+            self.key(false, "label")?;
+            self.string(label)?;
+        }
+        self.close(b'}', false)
+    }
+
+    fn open(&mut self, c: u8) -> io::Result<()> {
+        self.depth += 1;
+        self.w.write_all(&[c])
+    }
+
+    fn close(&mut self, c: u8, empty: bool) -> io::Result<()> {
+        self.depth -= 1;
+        if !empty {
+            self.newline()?;
+        }
+        self.w.write_all(&[c])
+    }
+
+    fn key(&mut self, first: bool, key: &str) -> io::Result<()> {
+        self.item(first)?;
+        self.string(key)?;
+        self.w.write_all(if self.pretty { b": " } else { b":" })
+    }
+
+    fn item(&mut self, first: bool) -> io::Result<()> {
+        if !first {
+            self.w.write_all(b",")?;
+        }
+        self.newline()
+    }
+
+    fn newline(&mut self) -> io::Result<()> {
+        if self.pretty {
+            write!(self.w, "\n{:1$}", "", self.depth * 2)?;
+        }
+        Ok(())
+    }
+
+    fn string(&mut self, s: &str) -> io::Result<()> {
+        self.w.write_all(b"\"")?;
+
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escape = match c {
+                '"' => "\\\"",
+                '\\' => "\\\\",
+                '\n' => "\\n",
+                '\r' => "\\r",
+                '\t' => "\\t",
+                '\u{8}' => "\\b",
+                '\u{c}' => "\\f",
+                c if c < ' ' => "",
+                _ => continue,
+            };
+
+            self.w.write_all(&s.as_bytes()[start..i])?;
+            if escape.is_empty() {
+                write!(self.w, "\\u{:04x}", c as u32)?;
+            } else {
+                self.w.write_all(escape.as_bytes())?;
+            }
+            start = i + c.len_utf8();
+        }
+
+        self.w.write_all(&s.as_bytes()[start..])?;
+        self.w.write_all(b"\"")
+    }
+}
+
+enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// How deeply objects and arrays may be nested. The document holds the outermost report, each
+/// report holds its children in the `stack` array, and the deepest report holds its spans in
+/// an array of objects.
+const MAX_DEPTH: usize = 2 * MAX_REPORT_DEPTH + 2;
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn document(&mut self) -> Result<Value, JsonError> {
+        let value = self.value()?;
+        self.whitespace();
+        match self.peek() {
+            None => Ok(value),
+            Some(_) => Err(self.error("trailing characters")),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, JsonError> {
+        self.whitespace();
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn nested<F>(&mut self, f: F) -> Result<Value, JsonError>
+    where
+        F: FnOnce(&mut Self) -> Result<Value, JsonError>,
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+
+        self.depth += 1;
+        let value = f(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+
+        self.whitespace();
+        if self.eat(b'}') {
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            self.whitespace();
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+
+            self.whitespace();
+            if self.eat(b'}') {
+                return Ok(Value::Object(fields));
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, JsonError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+
+        self.whitespace();
+        if self.eat(b']') {
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+
+            self.whitespace();
+            if self.eat(b']') {
+                return Ok(Value::Array(items));
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+
+        loop {
+            let start = self.pos;
+            while let Some(c) = self.peek() {
+                if c == b'"' || c == b'\\' || c < b' ' {
+                    break;
+                }
+                self.pos += 1;
+            }
+            // The run ends at an ASCII character or at the end of the input, which is a `str`.
+            out.push_str(
+                std::str::from_utf8(&self.input[start..self.pos])
+                    .expect("a run of non-ASCII characters is valid UTF-8"),
+            );
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    out.push(self.escape()?);
+                }
+                Some(_) => return Err(self.error("control character in string")),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, JsonError> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("unterminated string"))?;
+        self.pos += 1;
+
+        Ok(match c {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.hex4()?;
+                let code = if (0xd800..0xdc00).contains(&high) {
+                    if !(self.eat(b'\\') && self.eat(b'u')) {
+                        return Err(self.error("unpaired surrogate"));
+                    }
+                    let low = self.hex4()?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(self.error("unpaired surrogate"));
+                    }
+                    0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                } else {
+                    high
+                };
+                char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))?
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("invalid escape"));
+            }
+        })
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("checked to be hex digits"))
+    }
+
+    fn number(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+
+        self.eat(b'-');
+        if !self.digits() {
+            return Err(self.error("expected a digit"));
+        }
+        if self.eat(b'.') && !self.digits() {
+            return Err(self.error("expected a digit"));
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            if !self.eat(b'+') {
+                self.eat(b'-');
+            }
+            if !self.digits() {
+                return Err(self.error("expected a digit"));
+            }
+        }
+
+        std::str::from_utf8(&self.input[start..self.pos])
+            .ok()
+            .and_then(|n| n.parse().ok())
+            .map(Value::Number)
+            .ok_or_else(|| JsonError::at("invalid number", start))
+    }
+
+    /// Consumes a run of digits, returning whether there was at least one.
+    fn digits(&mut self) -> bool {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, JsonError> {
+        if self.input[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), JsonError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", c as char)))
+        }
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn error<S>(&self, msg: S) -> JsonError
+    where
+        S: Into<String>,
+    {
+        JsonError::at(msg, self.pos)
+    }
+}
@@ -1,22 +1,96 @@
-#[derive(Debug, Clone)]
+mod accumulator;
+mod builder;
+mod capture;
+mod code;
+mod collector;
+mod compat;
+mod ext;
+mod future;
+mod iter;
+mod json;
+mod macros;
+mod parallel;
+mod render;
+mod severity;
+mod sink;
+mod source;
+mod theme;
+mod zip;
+
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub use accumulator::Accumulator;
+pub use builder::EreportBuilder;
+pub use capture::{
+    captures_backtraces, captures_locations, set_capture_backtraces, set_capture_locations,
+    Location,
+};
+pub use code::{explain, lookup_code, register_code, registered_codes, CodeInfo};
+pub use collector::{CollectorScope, Order, SharedCollector};
+pub use compat::{install_eyre_hook, ErepHandler};
+pub use ext::{OptionExt, ResultExt};
+pub use future::{join_all, JoinAll};
+pub use json::{JsonError, JSON_VERSION};
+pub use parallel::par_collect;
+pub use render::{Charset, Numbering, Rendered, Renderer};
+pub use severity::Severity;
+pub use sink::{JsonLines, ReportSink, RingBuffer, Summary};
+pub use source::{register_source, source_text, Span};
+pub use theme::{Color, ColorChoice, Painted, Stream, Style, Theme};
+
+/// The message of a report for a step which returned `None`, when no message is given.
+pub(crate) const NONE_MSG: &str = "Got None, instead of Some";
+
+/// With the `serde` feature enabled, an `Erep` is serialized as a map with the value and the
+/// optional report, `{ "val": T, "rep": Ereport | null }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Erep<T> {
     val: T,
     rep: Option<Ereport>,
 }
 
 impl<T> Erep<T> {
+    /// Creates an `Erep` without a report.
+    pub fn ok(val: T) -> Self {
+        Erep { val, rep: None }
+    }
+
+    /// Creates an `Erep` with a report, for a value which is used despite the problem, like a
+    /// default.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Erep, Ereport};
+    ///
+    /// let port = Erep::with_report(8080, Ereport::warning("no port given, using 8080"));
+    ///
+    /// assert!(!port.has_errors());
+    /// assert_eq!(port.unwrap_with_err().0, 8080);
+    /// ```
+    pub fn with_report(val: T, rep: Ereport) -> Self {
+        Erep {
+            val,
+            rep: Some(rep),
+        }
+    }
+
     /// Maps a function F, on value T, returning U.
     ///
     /// Used if function F does not error, and if you want to keep the possible `Ereport`
     ///
     /// # Example
     /// ```rust
-    /// let err: Erep<i32> = Erep { val: 2, rep: None };
+    /// use erep::Erep;
+    ///
+    /// let err: Erep<i32> = Erep::ok(2);
     ///
-    /// err.vmap(|i| i + 2);
+    /// let (val, rep) = err.vmap(|i| i + 2).unwrap_with_err();
     ///
-    /// assert_eq!(err.val, 4);
-    /// assert_eq!(err.rep, None)
+    /// assert_eq!(val, 4);
+    /// assert!(rep.is_none())
     /// ```
     pub fn vmap<F, U>(self, f: F) -> Erep<U>
     where
@@ -35,13 +109,16 @@ impl<T> Erep<T> {
     ///
     /// # Example
     /// ```rust
-    /// let err: Erep<i32> = Erep { val: 2, rep: None };
+    /// use erep::Erep;
     ///
-    /// err.map(|i| Erep { val: i + 2, rep: None });
+    /// let err: Erep<i32> = Erep::ok(2);
     ///
-    /// assert_eq!(err.val, 4);
-    /// assert_eq!(err.rep, None)
+    /// let (val, rep) = err.map(|i| Erep::ok(i + 2)).unwrap_with_err();
+    ///
+    /// assert_eq!(val, 4);
+    /// assert!(rep.is_none())
     /// ```
+    #[track_caller]
     pub fn map<F, U>(self, f: F) -> Erep<U>
     where
         F: Fn(T) -> Erep<U>,
@@ -50,11 +127,7 @@ impl<T> Erep<T> {
         let (v, r) = f(vo).unwrap_with_err();
         Erep {
             val: v,
-            rep: Some(
-                vec![ro, r]
-                    .into_iter()
-                    .fold(Ereport::empty(), Ereport::push_opt),
-            ),
+            rep: Ereport::combine(ro, r),
         }
     }
 
@@ -66,32 +139,78 @@ impl<T> Erep<T> {
     ///
     /// # Example
     /// ```rust
-    /// let err: Erep<&str> = Erep { val: "2", rep: None };
+    /// use erep::Erep;
+    ///
+    /// let err: Erep<&str> = Erep::ok("2");
     ///
-    /// err.emap(|i| i.parse::<i32>());
+    /// let (val, rep) = err.emap(|i| i.parse::<i32>(), None).unwrap_with_err();
     ///
-    /// assert_eq!(err.val, 2);
-    /// assert_eq!(err.rep, None)
+    /// assert_eq!(val, Some(2));
+    /// assert!(rep.is_none())
     /// ```
+    #[track_caller]
     pub fn emap<F, U, E>(self, f: F, msg: Option<String>) -> Erep<Option<U>>
+    where
+        F: Fn(T) -> Result<U, E>,
+        E: std::fmt::Debug,
+    {
+        self.emap_with_code(f, msg, None)
+    }
+
+    /// Like `Erep::emap`, but the report created when F fails carries the given code, see
+    /// `register_code`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Erep;
+    ///
+    /// let port = Erep::ok("80x").emap_code(|s| s.parse::<u16>(), "E0042", None);
+    /// let (val, rep) = port.unwrap_with_err();
+    /// let rep = rep.unwrap();
+    ///
+    /// assert_eq!(val, None);
+    /// assert_eq!(rep.code(), Some("E0042"));
+    /// assert_eq!(
+    ///     rep.to_string(),
+    ///     "error[E0042]: ParseIntError { kind: InvalidDigit }"
+    /// );
+    /// ```
+    #[track_caller]
+    pub fn emap_code<F, U, E, C>(self, f: F, code: C, msg: Option<String>) -> Erep<Option<U>>
+    where
+        F: Fn(T) -> Result<U, E>,
+        E: std::fmt::Debug,
+        C: Into<String>,
+    {
+        self.emap_with_code(f, msg, Some(code.into()))
+    }
+
+    #[track_caller]
+    fn emap_with_code<F, U, E>(
+        self,
+        f: F,
+        msg: Option<String>,
+        code: Option<String>,
+    ) -> Erep<Option<U>>
     where
         F: Fn(T) -> Result<U, E>,
         E: std::fmt::Debug,
     {
         let (vo, ro) = self.unwrap_with_err();
 
-        match (f(vo), msg) {
-            (Ok(v), _) => Erep {
+        match f(vo) {
+            Ok(v) => Erep {
                 val: Some(v),
                 rep: ro,
             },
-            (Err(_), Some(m)) => Erep {
-                val: None::<U>,
-                rep: Some(Ereport::new(m)),
-            },
-            (Err(e), None) => Erep {
+            Err(e) => Erep {
                 val: None::<U>,
-                rep: Some(Ereport::new(format!("{e:?}"))),
+                rep: Ereport::failure(
+                    ro,
+                    msg.unwrap_or_else(|| format!("{e:?}")),
+                    code,
+                    capture::location(),
+                ),
             },
         }
     }
@@ -104,49 +223,265 @@ impl<T> Erep<T> {
     ///
     /// # Example
     /// ```rust
-    /// let err: Erep<&str> = Erep { val: "2", rep: None };
+    /// use erep::Erep;
     ///
-    /// err.omap(|i| i.parse::<i32>().ok());
+    /// let err: Erep<&str> = Erep::ok("2");
     ///
-    /// assert_eq!(err.val, 2);
-    /// assert_eq!(err.rep, None)
+    /// let (val, rep) = err.omap(|i| i.parse::<i32>().ok(), None).unwrap_with_err();
+    ///
+    /// assert_eq!(val, Some(2));
+    /// assert!(rep.is_none())
     /// ```
+    #[track_caller]
     pub fn omap<F, U>(self, f: F, msg: Option<String>) -> Erep<Option<U>>
     where
         F: Fn(T) -> Option<U>,
     {
-        match (f(self.val), msg) {
-            (Some(val), _) => Erep {
+        self.omap_with_code(f, msg, None)
+    }
+
+    /// Like `Erep::omap`, but the report created when F returns `None` carries the given
+    /// code, see `register_code`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Erep;
+    ///
+    /// let name = Erep::ok(None::<&str>).omap_code(|n| n, "E0007", Some("missing name".into()));
+    /// let (val, rep) = name.unwrap_with_err();
+    /// let rep = rep.unwrap();
+    ///
+    /// assert_eq!(val, None);
+    /// assert_eq!(rep.code(), Some("E0007"));
+    /// assert_eq!(rep.to_string(), "error[E0007]: missing name");
+    /// ```
+    #[track_caller]
+    pub fn omap_code<F, U, C>(self, f: F, code: C, msg: Option<String>) -> Erep<Option<U>>
+    where
+        F: Fn(T) -> Option<U>,
+        C: Into<String>,
+    {
+        self.omap_with_code(f, msg, Some(code.into()))
+    }
+
+    #[track_caller]
+    fn omap_with_code<F, U>(
+        self,
+        f: F,
+        msg: Option<String>,
+        code: Option<String>,
+    ) -> Erep<Option<U>>
+    where
+        F: Fn(T) -> Option<U>,
+    {
+        match f(self.val) {
+            Some(val) => Erep {
                 val: Some(val),
                 rep: self.rep,
             },
-            (None, None) => Erep {
+            None => Erep {
                 val: None::<U>,
-                rep: Some(Ereport::new("Got None, instead of Some")),
+                rep: Ereport::failure(
+                    self.rep,
+                    msg.unwrap_or_else(|| NONE_MSG.to_string()),
+                    code,
+                    capture::location(),
+                ),
             },
-            (None, Some(msg)) => Erep {
-                val: None::<U>,
-                rep: Some(Ereport::new(msg)),
+        }
+    }
+
+    /// Wraps the report, if there is one, in a new report with the given message, see
+    /// `Ereport::context`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Erep, Ereport, OptionExt};
+    ///
+    /// let port = None::<u16>
+    ///     .into_erep(8080)
+    ///     .context("while reading config");
+    /// let (_, rep) = port.unwrap_with_err();
+    ///
+    /// assert_eq!(
+    ///     rep.unwrap().to_string(),
+    ///     "while reading config\n└── [1] Got None, instead of Some"
+    /// );
+    ///
+    /// let port = Erep::ok(8080).context("while reading config");
+    /// assert!(port.is_clean());
+    ///
+    /// let ports = Erep::with_report(1, Ereport::warning("a"))
+    ///     .zip(Erep::with_report(2, Ereport::warning("b")))
+    ///     .context("ctx");
+    /// assert!(!ports.has_errors());
+    /// ```
+    #[track_caller]
+    pub fn context<S>(self, msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        match self.rep {
+            Some(rep) => Erep {
+                val: self.val,
+                rep: Some(rep.context(msg)),
             },
+            None => self,
         }
     }
 
+    /// Like `Erep::context`, but the message is only created if there is a report.
+    #[track_caller]
+    pub fn with_context<F, S>(self, f: F) -> Self
+    where
+        F: FnOnce() -> S,
+        S: Into<String>,
+    {
+        match self.rep {
+            Some(rep) => Erep {
+                val: self.val,
+                rep: Some(rep.context(f())),
+            },
+            None => self,
+        }
+    }
+
+    /// Whether the report contains any errors, ignoring warnings, notes and help messages, so
+    /// a computation with only warnings can still be considered a success.
+    pub fn has_errors(&self) -> bool {
+        self.rep.as_ref().is_some_and(Ereport::has_errors)
+    }
+
+    pub fn value(&self) -> &T {
+        &self.val
+    }
+
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.val
+    }
+
+    pub fn report(&self) -> Option<&Ereport> {
+        self.rep.as_ref()
+    }
+
+    pub fn report_mut(&mut self) -> &mut Option<Ereport> {
+        &mut self.rep
+    }
+
+    /// Whether there is no report at all, not even a warning.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Erep, Ereport};
+    ///
+    /// let mut port = Erep::ok(8080);
+    /// assert!(port.is_clean());
+    ///
+    /// *port.value_mut() += 1;
+    /// *port.report_mut() = Some(Ereport::warning("port was changed"));
+    ///
+    /// assert_eq!(*port.value(), 8081);
+    /// assert_eq!(port.report().unwrap().message(), "port was changed");
+    /// assert!(!port.is_clean());
+    /// ```
+    pub fn is_clean(&self) -> bool {
+        self.rep.is_none()
+    }
+
     pub fn unwrap_with_err(self) -> (T, Option<Ereport>) {
         (self.val, self.rep)
     }
 }
 
+/// An error report, a message together with the reports that led to it.
+///
+/// # Serialization
+/// With the `serde` feature enabled, a report is serialized as a map with its message and
+/// its children, recursively, `{ "msg": String, "stack": [Ereport] }`, followed by these
+/// optional fields, which are left out when they are not set:
+/// - `location`, where the report was created, `{ "file": String, "line": u32, "column": u32 }`
+/// - `severity`, one of `"help"`, `"note"`, `"warning"` or `"error"`, left out for errors
+/// - `code`, an error code like `"E0042"`
+/// - `spans`, the places in named sources the report points at,
+///   `[{ "source": String, "start": usize, "end": usize, "label": String }]`, where the label
+///   is optional
+/// - `metadata`, key-value pairs attached to the report, `{ String: String }`
+/// - `group`, `true` for a report which only groups its children, like `Ereport::empty`
+///
+/// The backtrace of a report is never serialized.
+///
+/// New fields are only ever added as optional ones, so data serialized by an older version
+/// of this crate can still be deserialized.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use erep::Ereport;
+///
+/// // Leave out the locations, to keep the output short.
+/// erep::set_capture_locations(false);
+///
+/// let rep = Ereport::new("invalid config").push(Ereport::new("missing key `name`"));
+/// let json = serde_json::to_string(&rep).unwrap();
+///
+/// assert_eq!(
+///     json,
+///     r#"{"msg":"invalid config","stack":[{"msg":"missing key `name`","stack":[]}]}"#
+/// );
+///
+/// let back: Ereport = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back.to_string(), rep.to_string());
+/// # }
+/// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ereport {
     msg: String,
     stack: Vec<Ereport>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    location: Option<Location>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    backtrace: Option<Arc<Backtrace>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Severity::is_error")
+    )]
+    severity: Severity,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    code: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    spans: Vec<Span>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    metadata: BTreeMap<String, String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    group: bool,
 }
 
 impl Ereport {
+    /// Creates a report without a message, which is only used to group other reports, so
+    /// no backtrace is captured for it.
+    #[track_caller]
     pub fn empty() -> Self {
         Self {
-            msg: String::new(),
-            stack: Vec::new(),
+            location: capture::location(),
+            group: true,
+            ..Self::bare(String::new())
         }
     }
 
@@ -156,6 +491,42 @@ impl Ereport {
         Self { stack, ..self }
     }
 
+    /// Creates a new report with the given message, whose only child is this report, like
+    /// `anyhow::Context`. The new report records the location of the caller, and takes the
+    /// highest severity in this report, so warnings stay warnings, even when merged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Ereport, Severity};
+    ///
+    /// let rep = Ereport::warning("file not found")
+    ///     .context("while reading config")
+    ///     .context("while starting up");
+    ///
+    /// assert_eq!(rep.severity(), Severity::Warning);
+    /// assert_eq!(rep.children()[0].message(), "while reading config");
+    /// assert_eq!(rep.children()[0].children()[0].message(), "file not found");
+    ///
+    /// let rep = Ereport::warning("unused key `host`")
+    ///     .merge(Ereport::warning("unused key `port`"))
+    ///     .context("while reading config");
+    ///
+    /// assert_eq!(rep.severity(), Severity::Warning);
+    /// assert!(!rep.has_errors());
+    /// ```
+    #[track_caller]
+    pub fn context<S>(self, msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            location: capture::location(),
+            severity: self.max_severity().unwrap_or(self.severity),
+            stack: vec![self],
+            ..Self::bare(msg.into())
+        }
+    }
+
     pub fn push_opt(self, other: Option<Ereport>) -> Self {
         match other {
             Some(e) => self.push(e),
@@ -163,13 +534,396 @@ impl Ereport {
         }
     }
 
+    /// Combines two reports into one, keeping both side by side, unlike `Ereport::push` which
+    /// nests `other` under this report.
+    ///
+    /// Reports without a message, like `Ereport::empty`, only group their children, so their
+    /// children are combined in their place. This makes `merge` associative, with the empty
+    /// report as its identity, and no report is ever lost.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// let a = Ereport::new("missing key `name`");
+    /// let b = Ereport::new("missing key `port`");
+    /// let c = Ereport::warning("unused key `host`");
+    ///
+    /// let abc = a.clone().merge(b.clone()).merge(c.clone());
+    ///
+    /// assert_eq!(abc, a.clone().merge(b.merge(c)));
+    /// assert_eq!(abc.children().len(), 3);
+    ///
+    /// assert_eq!(a.clone().merge(Ereport::empty()), a);
+    /// assert!(Ereport::empty().merge(Ereport::empty()).is_empty());
+    /// ```
+    #[track_caller]
+    pub fn merge(self, other: Ereport) -> Self {
+        Ereport::group(vec![self, other]).unwrap_or_else(Ereport::empty)
+    }
+
+    /// Like `Ereport::merge`, for reports which may be missing. The result is `None` when
+    /// there is nothing to report, so a clean computation stays clean.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// let rep = Ereport::new("missing key `name`");
+    ///
+    /// assert_eq!(Ereport::combine(None, None), None);
+    /// assert_eq!(Ereport::combine(Some(Ereport::empty()), None), None);
+    /// assert_eq!(Ereport::combine(None, Some(rep.clone())), Some(rep));
+    /// ```
+    #[track_caller]
+    pub fn combine(a: Option<Ereport>, b: Option<Ereport>) -> Option<Ereport> {
+        Ereport::group(a.into_iter().chain(b).collect())
+    }
+
+    /// Whether there is nothing to report, a report without a message whose children are all
+    /// empty as well.
+    pub fn is_empty(&self) -> bool {
+        self.is_group() && self.stack.iter().all(Ereport::is_empty)
+    }
+
+    /// Creates a report with the given message, recording the location of the caller, unless
+    /// disabled with `set_capture_locations`, and a backtrace, if enabled with
+    /// `set_capture_backtraces` or the environment.
+    #[track_caller]
     pub fn new<S>(msg: S) -> Self
     where
         S: Into<String>,
     {
         Self {
-            msg: msg.into(),
+            location: capture::location(),
+            backtrace: capture::backtrace(),
+            ..Self::bare(msg.into())
+        }
+    }
+
+    /// Creates a report with `Severity::Warning`, see `Ereport::new`.
+    #[track_caller]
+    pub fn warning<S>(msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(msg).with_severity(Severity::Warning)
+    }
+
+    /// Creates a report with `Severity::Note`, see `Ereport::new`.
+    #[track_caller]
+    pub fn note<S>(msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(msg).with_severity(Severity::Note)
+    }
+
+    /// Creates a report with `Severity::Help`, see `Ereport::new`.
+    #[track_caller]
+    pub fn help<S>(msg: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(msg).with_severity(Severity::Help)
+    }
+
+    /// Starts building a report with the given message, see `EreportBuilder`.
+    pub fn builder<S>(msg: S) -> EreportBuilder
+    where
+        S: Into<String>,
+    {
+        EreportBuilder::new(msg)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn message_mut(&mut self) -> &mut String {
+        &mut self.msg
+    }
+
+    /// The reports which led to this one, in the order they were pushed.
+    pub fn children(&self) -> &[Ereport] {
+        &self.stack
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<Ereport> {
+        &mut self.stack
+    }
+
+    /// Attaches a key-value pair to the report, replacing any previous value for the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::Ereport;
+    ///
+    /// let rep = Ereport::new("request failed")
+    ///     .with_metadata("status", "503")
+    ///     .with_metadata("retries", "3");
+    ///
+    /// assert_eq!(rep.to_string(), "request failed\n= retries: 3\n= status: 503");
+    /// ```
+    pub fn with_metadata<K, V>(self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut metadata = self.metadata;
+        metadata.insert(key.into(), value.into());
+        Self { metadata, ..self }
+    }
+
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.metadata
+    }
+
+    pub fn with_severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Attaches an error code, like `E0042`, to the report, see `register_code`.
+    pub fn with_code<C>(self, code: C) -> Self
+    where
+        C: Into<String>,
+    {
+        Self {
+            code: Some(code.into()),
+            ..self
+        }
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Points the report at a place in a named source, see `register_source`.
+    pub fn with_span(self, span: Span) -> Self {
+        let mut spans = self.spans;
+        spans.push(span);
+        Self { spans, ..self }
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// The highest severity of this report and all of its children, or `None` if there are
+    /// only grouping reports, like the ones created by `Ereport::empty`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Ereport, Severity};
+    ///
+    /// let rep = Ereport::empty()
+    ///     .push(Ereport::warning("unused key `port`"))
+    ///     .push(Ereport::note("defaults are used").push(Ereport::help("add a `port` key")));
+    ///
+    /// assert_eq!(rep.max_severity(), Some(Severity::Warning));
+    /// assert!(!rep.has_errors());
+    /// assert_eq!(Ereport::empty().max_severity(), None);
+    /// ```
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.reports().into_iter().map(|r| r.severity).max()
+    }
+
+    /// The number of reports of each severity, in this report and all of its children,
+    /// leaving out grouping reports. Severities without any reports are left out.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Ereport, Severity};
+    ///
+    /// let rep = Ereport::new("invalid config")
+    ///     .push(Ereport::new("missing key `name`"))
+    ///     .push(Ereport::warning("unused key `port`"));
+    ///
+    /// let counts = rep.count_by_severity();
+    ///
+    /// assert_eq!(counts[&Severity::Error], 2);
+    /// assert_eq!(counts[&Severity::Warning], 1);
+    /// assert_eq!(counts.get(&Severity::Note), None);
+    /// ```
+    pub fn count_by_severity(&self) -> BTreeMap<Severity, usize> {
+        let mut counts = BTreeMap::new();
+        for rep in self.reports() {
+            *counts.entry(rep.severity).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Whether this report or any of its children is an error.
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Groups reports under a new report without a message. Grouping reports among them are
+    /// replaced by their entries, a single report is returned as is, and `None` if there are
+    /// none, so every combinator produces the same shape, see `Ereport::merge`.
+    #[track_caller]
+    pub(crate) fn group(reps: Vec<Ereport>) -> Option<Ereport> {
+        Ereport::group_at(reps, capture::location())
+    }
+
+    /// Like `Ereport::group`, with a location captured earlier, for code which runs away from
+    /// its caller, like a future.
+    pub(crate) fn group_at(reps: Vec<Ereport>, location: Option<Location>) -> Option<Ereport> {
+        let mut entries: Vec<Ereport> = reps.into_iter().flat_map(Ereport::into_entries).collect();
+        match entries.len() {
+            0 => None,
+            1 => entries.pop(),
+            _ => Some(Ereport {
+                stack: entries,
+                location,
+                group: true,
+                ..Ereport::bare(String::new())
+            }),
+        }
+    }
+
+    /// The prior report, together with a new one for a failed step at the given location,
+    /// shared by the combinators like `Erep::emap` and their async versions.
+    pub(crate) fn failure(
+        prior: Option<Ereport>,
+        msg: String,
+        code: Option<String>,
+        location: Option<Location>,
+    ) -> Option<Ereport> {
+        let rep = Ereport {
+            location: location.clone(),
+            backtrace: capture::backtrace(),
+            code,
+            ..Ereport::bare(msg)
+        };
+        Ereport::group_at(prior.into_iter().chain([rep]).collect(), location)
+    }
+
+    /// The severity of a report wrapping the given ones, the highest among them, so wrapping
+    /// only warnings does not turn them into an error.
+    pub(crate) fn wrapping_severity(reps: &[Ereport]) -> Severity {
+        reps.iter()
+            .filter_map(Ereport::max_severity)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// A report with only a message, and nothing captured.
+    pub(crate) fn bare(msg: String) -> Self {
+        Self {
+            msg,
             stack: Vec::new(),
+            location: None,
+            backtrace: None,
+            severity: Severity::Error,
+            code: None,
+            spans: Vec::new(),
+            metadata: BTreeMap::new(),
+            group: false,
         }
     }
+
+    /// Where the report was created, if capturing locations was enabled at the time.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The backtrace of the thread which created the report, if capturing backtraces was
+    /// enabled at the time.
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_deref()
+    }
+
+    /// A report created by `Ereport::empty` or `Ereport::group`, which only groups its
+    /// children, as long as nothing else was added to it since. Other reports are never
+    /// groups, even without a message, so flattening groups does not lose them.
+    pub(crate) fn is_group(&self) -> bool {
+        self.group
+            && self.msg.is_empty()
+            && self.code.is_none()
+            && self.spans.is_empty()
+            && self.metadata.is_empty()
+    }
+
+    /// Like `Ereport::entries`, but taking ownership. A grouping report is replaced by its
+    /// entries, any other report is kept as is.
+    pub(crate) fn into_entries(self) -> Vec<Ereport> {
+        if self.is_group() {
+            self.stack
+                .into_iter()
+                .flat_map(Ereport::into_entries)
+                .collect()
+        } else {
+            vec![self]
+        }
+    }
+
+    /// This report and all of its children, recursively, leaving out grouping reports.
+    pub(crate) fn reports(&self) -> Vec<&Ereport> {
+        let mut reports = if self.is_group() { vec![] } else { vec![self] };
+        for child in &self.stack {
+            reports.extend(child.reports());
+        }
+        reports
+    }
+
+    /// The children of this report, with the children of any grouping reports taking their
+    /// place.
+    pub(crate) fn entries(&self) -> Vec<&Ereport> {
+        self.stack
+            .iter()
+            .flat_map(|e| if e.is_group() { e.entries() } else { vec![e] })
+            .collect()
+    }
+}
+
+/// Reports are equal if they have the same contents, ignoring where they were created and
+/// their backtraces.
+impl PartialEq for Ereport {
+    fn eq(&self, other: &Self) -> bool {
+        self.msg == other.msg
+            && self.stack == other.stack
+            && self.severity == other.severity
+            && self.code == other.code
+            && self.spans == other.spans
+            && self.metadata == other.metadata
+            && self.is_group() == other.is_group()
+    }
+}
+
+impl Eq for Ereport {}
+
+/// The source of a report is its first child, so walking `source` follows the first branch
+/// of the tree down to a leaf. The `Display` implementation already renders every branch.
+///
+/// # Example
+/// ```rust
+/// use std::error::Error;
+///
+/// use erep::Ereport;
+///
+/// fn load() -> Result<(), Box<dyn Error + Send + Sync>> {
+///     Err(Ereport::new("failed to load config").push(Ereport::new("missing key `name`")))?
+/// }
+///
+/// let err = load().unwrap_err();
+/// let source = err.source().unwrap();
+///
+/// assert_eq!(source.to_string(), "missing key `name`");
+/// assert!(source.source().is_none());
+/// ```
+impl std::error::Error for Ereport {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.entries()
+            .first()
+            .map(|e| *e as &(dyn std::error::Error + 'static))
+    }
 }
@@ -0,0 +1,101 @@
+/// Evaluates a `Result`, recording the error in the given `Accumulator` and continuing with
+/// the fallback value, instead of returning like `?`.
+///
+/// The report uses the `Debug` output of the error as its message. With a fourth argument,
+/// the report gets that message instead, and the error is pushed onto it.
+///
+/// # Example
+/// ```rust
+/// use erep::{erep_collect, erep_try};
+///
+/// let ports = erep_collect!(acc => {
+///     let http: u16 = erep_try!(acc, "80".parse(), 80);
+///     let https: u16 = erep_try!(acc, "4433x".parse(), 443, "invalid https port");
+///     (http, https)
+/// });
+///
+/// let (ports, rep) = ports.unwrap_with_err();
+/// let rep = rep.unwrap();
+///
+/// assert_eq!(ports, (80, 443));
+/// assert_eq!(rep.message(), "invalid https port");
+/// assert_eq!(
+///     rep.children()[0].message(),
+///     "ParseIntError { kind: InvalidDigit }"
+/// );
+/// ```
+#[macro_export]
+macro_rules! erep_try {
+    ($acc:expr, $expr:expr, $default:expr $(,)?) => {
+        match $expr {
+            ::std::result::Result::Ok(val) => val,
+            ::std::result::Result::Err(e) => {
+                $acc.push($crate::Ereport::new(::std::format!("{e:?}")));
+                $default
+            }
+        }
+    };
+    ($acc:expr, $expr:expr, $default:expr, $msg:expr $(,)?) => {
+        match $expr {
+            ::std::result::Result::Ok(val) => val,
+            ::std::result::Result::Err(e) => {
+                $acc.push(
+                    $crate::Ereport::new($msg).push($crate::Ereport::new(::std::format!("{e:?}"))),
+                );
+                $default
+            }
+        }
+    };
+}
+
+/// Evaluates a block with a fresh `Accumulator` bound to the given name, and returns the
+/// value of the block as an `Erep`, together with everything recorded in it, like
+/// `Accumulator::finish`.
+///
+/// The accumulator is an ordinary local variable, so the block can also use it directly,
+/// and `.await` inside an async block works as expected.
+///
+/// # Example
+/// ```rust
+/// use erep::{erep_collect, erep_try, Erep, Ereport};
+///
+/// let sum = erep_collect!(acc => {
+///     let mut sum = 0;
+///     for s in ["1", "two", "3", "four"] {
+///         sum += erep_try!(acc, s.parse::<i32>(), 0);
+///     }
+///     if sum < 5 {
+///         acc.push(Ereport::warning("sum is small"));
+///     }
+///     sum
+/// });
+///
+/// let (sum, rep) = sum.unwrap_with_err();
+///
+/// assert_eq!(sum, 4);
+/// assert_eq!(rep.unwrap().children().len(), 3);
+///
+/// let clean = erep_collect!(acc => { erep_try!(acc, "1".parse::<i32>(), 0) });
+/// assert!(clean.is_clean());
+///
+/// async fn fetch(key: &str) -> Result<u16, String> {
+///     key.parse().map_err(|_| format!("no such key `{key}`"))
+/// }
+///
+/// async fn load() -> Erep<(u16, u16)> {
+///     erep_collect!(acc => {
+///         let a = erep_try!(acc, fetch("1").await, 0);
+///         let b = erep_try!(acc, fetch("b").await, 0);
+///         (a, b)
+///     })
+/// }
+/// ```
+#[macro_export]
+macro_rules! erep_collect {
+    ($acc:ident => $body:block) => {{
+        #[allow(unused_mut)]
+        let mut $acc = $crate::Accumulator::new();
+        let val = $body;
+        $acc.finish(val)
+    }};
+}
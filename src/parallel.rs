@@ -0,0 +1,74 @@
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::{Erep, Ereport};
+
+/// Runs F on every element of the slice, spread over `thread::available_parallelism`
+/// threads, and collects the values in the order of the slice, keeping the reports of all of
+/// them in the same order, like collecting an iterator of `Erep`s.
+///
+/// A panic in F is resumed on the calling thread.
+///
+/// # Example
+/// ```rust
+/// use erep::{par_collect, Erep, Ereport};
+///
+/// let records: Vec<i64> = (0..1000).collect();
+///
+/// let squares = par_collect(&records, |&n| {
+///     if n % 250 == 0 {
+///         Erep::with_report(0, Ereport::new(format!("record {n} is invalid")))
+///     } else {
+///         Erep::ok(n * n)
+///     }
+/// });
+/// let (val, rep) = squares.unwrap_with_err();
+/// let rep = rep.unwrap();
+/// let messages: Vec<_> = rep.children().iter().map(|r| r.message()).collect();
+///
+/// assert_eq!(val[3], 9);
+/// assert_eq!(val.len(), 1000);
+/// assert_eq!(
+///     messages,
+///     ["record 0 is invalid", "record 250 is invalid", "record 500 is invalid", "record 750 is invalid"]
+/// );
+/// ```
+#[track_caller]
+pub fn par_collect<T, U, F>(items: &[T], f: F) -> Erep<Vec<U>>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> Erep<U> + Sync,
+{
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = items.len().div_ceil(workers).max(1);
+
+    let chunks: Vec<Vec<Erep<U>>> = if workers == 1 || items.len() <= 1 {
+        vec![items.iter().map(&f).collect()]
+    } else {
+        let f = &f;
+        thread::scope(|s| {
+            let handles: Vec<_> = items
+                .chunks(chunk_size)
+                .map(|chunk| s.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        })
+    };
+
+    let mut vals = Vec::with_capacity(items.len());
+    let mut reps = Vec::new();
+    for erep in chunks.into_iter().flatten() {
+        let (val, rep) = erep.unwrap_with_err();
+        vals.push(val);
+        reps.extend(rep);
+    }
+
+    Erep {
+        val: vals,
+        rep: Ereport::group(reps),
+    }
+}
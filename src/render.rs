@@ -0,0 +1,475 @@
+use std::backtrace::BacktraceStatus;
+use std::fmt;
+
+use crate::{source_text, ColorChoice, Ereport, Severity, Span, Stream, Style, Theme};
+
+/// The set of glyphs used to draw the tree guides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    /// Box-drawing characters, `├──`, `└──` and `│`.
+    #[default]
+    Unicode,
+    /// Plain ASCII, `|--`, `` `-- `` and `|`, for terminals without Unicode support.
+    Ascii,
+}
+
+impl Charset {
+    fn tee(self) -> &'static str {
+        match self {
+            Charset::Unicode => "├── ",
+            Charset::Ascii => "|-- ",
+        }
+    }
+
+    fn elbow(self) -> &'static str {
+        match self {
+            Charset::Unicode => "└── ",
+            Charset::Ascii => "`-- ",
+        }
+    }
+
+    fn pipe(self) -> &'static str {
+        match self {
+            Charset::Unicode => "│   ",
+            Charset::Ascii => "|   ",
+        }
+    }
+
+    fn blank(self) -> &'static str {
+        "    "
+    }
+}
+
+/// How entries in the tree are numbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Numbering {
+    /// No numbers, only the tree guides.
+    None,
+    /// The position among siblings, `[2]`.
+    Sibling,
+    /// The full path from the root, `[1.2]`, which also shows the depth of the entry.
+    #[default]
+    Path,
+}
+
+/// Renders an `Ereport` as an indented tree.
+///
+/// Grouping reports, like the ones created by `Ereport::empty` or `Ereport::merge`, are not
+/// drawn themselves, and their children take their place. Any other report with an empty
+/// message is drawn as `(no message)`, so it is not mistaken for a blank line.
+///
+/// The message of a report is prefixed by its severity and code, like `warning: ` or
+/// `error[E0042]: `, except for errors without a code.
+///
+/// # Example
+/// ```rust
+/// use erep::{Charset, Ereport, Numbering, Renderer};
+///
+/// let rep = Ereport::new("failed to load config")
+///     .push(Ereport::new("missing key `name`"))
+///     .push(Ereport::new("invalid port").push(Ereport::new("not a number")));
+///
+/// let renderer = Renderer::new()
+///     .charset(Charset::Ascii)
+///     .numbering(Numbering::Path);
+///
+/// assert_eq!(
+///     renderer.render(&rep).to_string(),
+///     "failed to load config\n\
+///      |-- [1] missing key `name`\n\
+///      `-- [2] invalid port\n    \
+///          `-- [2.1] not a number"
+/// );
+///
+/// let blank = Ereport::new("failed to load config").push(Ereport::new(""));
+/// assert_eq!(
+///     Renderer::new().render(&blank).to_string(),
+///     "failed to load config\n└── [1] (no message)"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    charset: Charset,
+    numbering: Numbering,
+    snippets: bool,
+    locations: bool,
+    backtraces: bool,
+    theme: Theme,
+    color: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            charset: Charset::default(),
+            numbering: Numbering::default(),
+            snippets: true,
+            locations: false,
+            backtraces: false,
+            theme: Theme::default(),
+            color: false,
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the glyphs used to draw the tree guides.
+    pub fn charset(self, charset: Charset) -> Self {
+        Self { charset, ..self }
+    }
+
+    /// Sets how entries are numbered.
+    pub fn numbering(self, numbering: Numbering) -> Self {
+        Self { numbering, ..self }
+    }
+
+    /// Sets whether the source lines pointed at by the spans of each report are shown, below
+    /// its message. Enabled by default.
+    pub fn snippets(self, snippets: bool) -> Self {
+        Self { snippets, ..self }
+    }
+
+    /// Sets whether the location each report was created at is shown, below its message.
+    pub fn locations(self, locations: bool) -> Self {
+        Self { locations, ..self }
+    }
+
+    /// Sets whether captured backtraces are shown. Only the backtraces of leaves are shown,
+    /// since a report higher up in the tree was created after its children, while the error
+    /// was already being handled.
+    pub fn backtraces(self, backtraces: bool) -> Self {
+        Self { backtraces, ..self }
+    }
+
+    /// Sets the styles used when colors are enabled.
+    pub fn theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }
+    }
+
+    /// Sets whether the output is styled with ANSI escape codes. Disabled by default.
+    pub fn color(self, color: bool) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Enables colors if `choice` allows it for the given stream, see `ColorChoice`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{ColorChoice, Ereport, Renderer, Stream, Theme};
+    ///
+    /// let renderer = Renderer::new()
+    ///     .theme(Theme::monochrome())
+    ///     .auto_color(ColorChoice::Always, Stream::Stderr);
+    ///
+    /// let rep = Ereport::warning("unused key `port`");
+    ///
+    /// assert_eq!(
+    ///     renderer.render(&rep).to_string(),
+    ///     "\x1b[1mwarning\x1b[0m\x1b[1m: unused key `port`\x1b[0m"
+    /// );
+    /// ```
+    pub fn auto_color(self, choice: ColorChoice, stream: Stream) -> Self {
+        self.color(choice.enabled_for(stream))
+    }
+
+    /// Returns a value implementing `Display`, which renders the report with this renderer.
+    pub fn render<'a>(&'a self, report: &'a Ereport) -> Rendered<'a> {
+        Rendered {
+            renderer: self,
+            report,
+        }
+    }
+
+    fn write_report(&self, f: &mut impl fmt::Write, report: &Ereport) -> fmt::Result {
+        if report.is_group() {
+            // Without a message to act as a root, every entry becomes a root of its own.
+            for (i, entry) in report.entries().into_iter().enumerate() {
+                let path = [i + 1];
+                self.write_message(f, "", "", &self.label(&path), entry)?;
+                self.write_children(f, "", &path, entry)?;
+            }
+            Ok(())
+        } else {
+            self.write_message(f, "", "", "", report)?;
+            self.write_children(f, "", &[], report)
+        }
+    }
+
+    fn write_children(
+        &self,
+        f: &mut impl fmt::Write,
+        prefix: &str,
+        path: &[usize],
+        report: &Ereport,
+    ) -> fmt::Result {
+        let entries = report.entries();
+        let last = entries.len().saturating_sub(1);
+
+        for (i, entry) in entries.into_iter().enumerate() {
+            let (connector, guide) = if i == last {
+                (self.charset.elbow(), self.charset.blank())
+            } else {
+                (self.charset.tee(), self.charset.pipe())
+            };
+
+            let mut child_path = path.to_vec();
+            child_path.push(i + 1);
+
+            let child_prefix = format!("{prefix}{guide}");
+            self.write_message(
+                f,
+                &format!("{prefix}{connector}"),
+                &child_prefix,
+                &self.label(&child_path),
+                entry,
+            )?;
+            self.write_children(f, &child_prefix, &child_path, entry)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the message of a single entry, followed by any details shown for it, aligning
+    /// continuation lines with the first.
+    fn write_message(
+        &self,
+        f: &mut impl fmt::Write,
+        lead: &str,
+        guide: &str,
+        label: &str,
+        report: &Ereport,
+    ) -> fmt::Result {
+        let guide_style = self.style(self.theme.guide);
+        let message_style = self.style(self.theme.message);
+        let detail_style = self.style(self.theme.detail);
+
+        let mut lines = report.msg.lines();
+        write!(f, "{}", guide_style.paint(format_args!("{lead}{label}")))?;
+        let first = lines.next().unwrap_or_default();
+        let severity_style = self.style(self.theme.severity(report.severity));
+        match heading(report) {
+            Some(heading) if report.msg.is_empty() => writeln!(
+                f,
+                "{}{}{}",
+                severity_style.paint(heading),
+                message_style.paint(": "),
+                detail_style.paint("(no message)")
+            )?,
+            Some(heading) => writeln!(
+                f,
+                "{}{}",
+                severity_style.paint(heading),
+                message_style.paint(format_args!(": {first}"))
+            )?,
+            None if report.msg.is_empty() => writeln!(f, "{}", detail_style.paint("(no message)"))?,
+            None => writeln!(f, "{}", message_style.paint(first))?,
+        }
+
+        let indent = " ".repeat(label.chars().count());
+        let guide = guide_style
+            .paint(format_args!("{guide}{indent}"))
+            .to_string();
+        for line in lines {
+            writeln!(f, "{guide}{}", message_style.paint(line))?;
+        }
+
+        if self.snippets {
+            for span in &report.spans {
+                self.write_snippet(f, &guide, report.severity, span)?;
+            }
+        }
+
+        for (key, value) in &report.metadata {
+            writeln!(
+                f,
+                "{guide}{}",
+                detail_style.paint(format_args!("= {key}: {value}"))
+            )?;
+        }
+
+        if let Some(location) = report.location.as_ref().filter(|_| self.locations) {
+            writeln!(
+                f,
+                "{guide}{}",
+                detail_style.paint(format_args!("at {location}"))
+            )?;
+        }
+
+        let backtrace = report
+            .backtrace
+            .as_deref()
+            .filter(|_| self.backtraces && report.stack.is_empty())
+            .filter(|b| b.status() == BacktraceStatus::Captured);
+        if let Some(backtrace) = backtrace {
+            writeln!(f, "{guide}{}", detail_style.paint("stack backtrace:"))?;
+            for line in backtrace.to_string().lines() {
+                writeln!(f, "{guide}{}", detail_style.paint(line))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the lines of the source pointed at by a span, underlining the span, or just the
+    /// name of the source and the byte range, if the source is not registered.
+    fn write_snippet(
+        &self,
+        f: &mut impl fmt::Write,
+        prefix: &str,
+        severity: Severity,
+        span: &Span,
+    ) -> fmt::Result {
+        let detail = self.style(self.theme.detail);
+        let underline = self.style(self.theme.severity(severity));
+
+        let Some(text) = source_text(span.source()) else {
+            let range = span.range();
+            write!(
+                f,
+                "{prefix}{}",
+                detail.paint(format_args!("--> {}, bytes {range:?}", span.source()))
+            )?;
+            return match span.label() {
+                Some(label) => writeln!(f, ": {}", underline.paint(label)),
+                None => writeln!(f),
+            };
+        };
+
+        let start = floor_char_boundary(&text, span.range().start);
+        let end = floor_char_boundary(&text, span.range().end).max(start);
+
+        // Every line, with the byte offset it starts at.
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            lines.push((offset, line.trim_end_matches(['\n', '\r'])));
+            offset += line.len();
+        }
+        if lines.is_empty() {
+            lines.push((0, ""));
+        }
+
+        let line_of = |pos: usize| lines.iter().rposition(|(o, _)| *o <= pos).unwrap_or(0);
+        let first = line_of(start);
+        let last = line_of(end.saturating_sub(1).max(start));
+        let width = (last + 1).to_string().len();
+        let pad = " ".repeat(width);
+
+        let (first_offset, first_line) = lines[first];
+        let column = first_line
+            .get(..(start - first_offset).min(first_line.len()))
+            .map_or(0, |s| s.chars().count());
+        writeln!(
+            f,
+            "{prefix}{}",
+            detail.paint(format_args!(
+                "{pad}--> {}:{}:{}",
+                span.source(),
+                first + 1,
+                column + 1
+            ))
+        )?;
+        writeln!(f, "{prefix}{}", detail.paint(format_args!("{pad} |")))?;
+
+        for (i, (line_offset, line)) in lines.iter().enumerate().take(last + 1).skip(first) {
+            writeln!(
+                f,
+                "{prefix}{} {line}",
+                detail.paint(format_args!("{:>width$} |", i + 1))
+            )?;
+
+            let chars_until = |pos: usize| {
+                let pos = pos.clamp(*line_offset, line_offset + line.len()) - line_offset;
+                line.get(..pos).map_or(0, |s| s.chars().count())
+            };
+            let from = chars_until(start);
+            let to = chars_until(end).max(from + 1);
+
+            write!(
+                f,
+                "{prefix}{} {}{}",
+                detail.paint(format_args!("{pad} |")),
+                " ".repeat(from),
+                underline.paint("^".repeat(to - from))
+            )?;
+            match span.label().filter(|_| i == last) {
+                Some(label) => writeln!(f, " {}", underline.paint(label))?,
+                None => writeln!(f)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The style to use, which is plain if colors are disabled.
+    fn style(&self, style: Style) -> Style {
+        if self.color {
+            style
+        } else {
+            Style::new()
+        }
+    }
+
+    fn label(&self, path: &[usize]) -> String {
+        match (self.numbering, path) {
+            (_, []) | (Numbering::None, _) => String::new(),
+            (Numbering::Sibling, [.., n]) => format!("[{n}] "),
+            (Numbering::Path, path) => {
+                let path: Vec<String> = path.iter().map(usize::to_string).collect();
+                format!("[{}] ", path.join("."))
+            }
+        }
+    }
+}
+
+/// The severity and code shown before the message of a report. Errors are the default, so
+/// their severity is left out, unless they have a code.
+fn heading(report: &Ereport) -> Option<String> {
+    match (report.severity, &report.code) {
+        (Severity::Error, None) => None,
+        (severity, None) => Some(severity.to_string()),
+        (severity, Some(code)) => Some(format!("{severity}[{code}]")),
+    }
+}
+
+/// The largest char boundary in `text` which is not after `pos`.
+fn floor_char_boundary(text: &str, pos: usize) -> usize {
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+/// An `Ereport` paired with a `Renderer`, created by `Renderer::render`.
+#[derive(Debug, Clone, Copy)]
+pub struct Rendered<'a> {
+    renderer: &'a Renderer,
+    report: &'a Ereport,
+}
+
+impl fmt::Display for Rendered<'_> {
+    /// Every line is terminated, except the last one, so the output can be embedded like any
+    /// other error message.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        self.renderer.write_report(&mut out, self.report)?;
+        f.write_str(out.strip_suffix('\n').unwrap_or(&out))
+    }
+}
+
+impl fmt::Display for Ereport {
+    /// Renders the report as a tree, using the default `Renderer`. The alternate flag, `{:#}`,
+    /// also shows where each report was created, and any captured backtraces.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Renderer::default()
+            .locations(f.alternate())
+            .backtraces(f.alternate())
+            .render(self)
+            .fmt(f)
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// How severe a report is, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// A suggestion on how to fix a problem.
+    Help,
+    /// Additional information about a problem.
+    Note,
+    /// A problem which does not stop the computation from succeeding.
+    Warning,
+    /// A problem which makes the computation fail.
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// All severities, from least to most severe.
+    pub const ALL: [Severity; 4] = [
+        Severity::Help,
+        Severity::Note,
+        Severity::Warning,
+        Severity::Error,
+    ];
+
+    /// The lowercase name of the severity, as used by the renderers.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Help => "help",
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    /// Parses the lowercase name of a severity, the inverse of `Severity::as_str`.
+    pub fn from_name(name: &str) -> Option<Severity> {
+        Severity::ALL.into_iter().find(|s| s.as_str() == name)
+    }
+
+    pub(crate) fn is_error(&self) -> bool {
+        *self == Severity::Error
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{self, Write};
+use std::sync::mpsc;
+
+use crate::{capture, Ereport, Severity};
+
+/// Somewhere reports can be sent as they are produced, so long-running jobs do not have to
+/// hold all of them in memory, see `Accumulator::flush_into`.
+///
+/// # Example
+/// ```rust
+/// use erep::{Accumulator, Ereport, ReportSink, RingBuffer};
+///
+/// let mut sink = RingBuffer::new(2);
+/// let mut acc = Accumulator::new();
+///
+/// for record in ["1", "x", "3", "y", "z"] {
+///     acc.check(record.parse::<i32>());
+///     acc.flush_into(&mut sink).unwrap();
+/// }
+///
+/// assert!(acc.is_empty());
+/// assert_eq!(sink.dropped(), 1);
+/// assert_eq!(sink.reports().count(), 2);
+/// ```
+pub trait ReportSink {
+    /// Takes a single report. The report is only borrowed, so the caller still has it if
+    /// this fails.
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()>;
+
+    /// Makes sure every report accepted so far has reached its destination.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S> ReportSink for &mut S
+where
+    S: ReportSink + ?Sized,
+{
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()> {
+        (**self).accept(rep)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+}
+
+impl ReportSink for Vec<Ereport> {
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()> {
+        self.push(rep.clone());
+        Ok(())
+    }
+}
+
+/// Sends the reports to the receiving end, failing with `io::ErrorKind::BrokenPipe` once it
+/// is gone.
+///
+/// # Example
+/// ```rust
+/// use std::io;
+/// use std::sync::mpsc;
+///
+/// use erep::{Accumulator, Ereport};
+///
+/// let (mut tx, rx) = mpsc::channel();
+/// let mut acc = Accumulator::new();
+///
+/// acc.push(Ereport::new("missing key `name`"));
+/// acc.flush_into(&mut tx).unwrap();
+/// assert_eq!(rx.recv().unwrap().message(), "missing key `name`");
+///
+/// drop(rx);
+/// acc.push(Ereport::new("missing key `port`"));
+/// let err = acc.flush_into(&mut tx).unwrap_err();
+///
+/// assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+/// assert_eq!(acc.reports()[0].message(), "missing key `port`");
+/// ```
+impl ReportSink for mpsc::Sender<Ereport> {
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()> {
+        self.send(rep.clone())
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "the receiver was dropped"))
+    }
+}
+
+/// Writes each report as a JSON document on its own line, in the format of
+/// `Ereport::to_json`.
+///
+/// # Example
+/// ```rust
+/// use erep::{Ereport, JsonLines, ReportSink};
+///
+/// erep::set_capture_locations(false);
+///
+/// let mut sink = JsonLines::new(Vec::new());
+/// sink.accept(&Ereport::new("a")).unwrap();
+/// sink.accept(&Ereport::warning("b")).unwrap();
+///
+/// let out = String::from_utf8(sink.into_inner()).unwrap();
+/// let lines: Vec<_> = out.lines().collect();
+///
+/// assert_eq!(lines.len(), 2);
+/// assert_eq!(Ereport::from_json(lines[1]).unwrap().message(), "b");
+/// ```
+#[derive(Debug)]
+pub struct JsonLines<W>
+where
+    W: Write,
+{
+    w: W,
+}
+
+impl<W> JsonLines<W>
+where
+    W: Write,
+{
+    pub fn new(w: W) -> Self {
+        Self { w }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+}
+
+impl<W> ReportSink for JsonLines<W>
+where
+    W: Write,
+{
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()> {
+        rep.write_json(&mut self.w)?;
+        self.w.write_all(b"\n")
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.w.flush()
+    }
+}
+
+/// Keeps the most recent reports in memory, dropping the oldest once it is full.
+#[derive(Debug, Clone)]
+pub struct RingBuffer {
+    reps: VecDeque<Ereport>,
+    capacity: usize,
+    dropped: usize,
+}
+
+impl RingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            reps: VecDeque::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    /// The reports which are kept, from oldest to newest.
+    pub fn reports(&self) -> impl Iterator<Item = &Ereport> {
+        self.reps.iter()
+    }
+
+    /// How many reports were dropped to make room for newer ones.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn into_vec(self) -> Vec<Ereport> {
+        self.reps.into()
+    }
+}
+
+impl ReportSink for RingBuffer {
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()> {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return Ok(());
+        }
+        if self.reps.len() == self.capacity {
+            self.reps.pop_front();
+            self.dropped += 1;
+        }
+        self.reps.push_back(rep.clone());
+        Ok(())
+    }
+}
+
+/// Passes reports on to another sink, while counting them and keeping the first few, to
+/// build a single report summarizing a whole job at the end.
+///
+/// # Example
+/// ```rust
+/// use erep::{Ereport, ReportSink, Severity, Summary};
+///
+/// let mut sink = Summary::new(Vec::new(), 2);
+/// for i in 0..5 {
+///     sink.accept(&Ereport::new(format!("record {i} is invalid"))).unwrap();
+/// }
+/// sink.accept(&Ereport::warning("record 5 is empty")).unwrap();
+///
+/// let (summary, all) = sink.finish("importing records");
+///
+/// assert_eq!(all.len(), 6);
+/// assert_eq!(summary.severity(), Severity::Error);
+/// assert_eq!(summary.children().len(), 2);
+/// assert_eq!(summary.metadata()["error"], "5");
+/// assert_eq!(summary.metadata()["warning"], "1");
+/// assert_eq!(summary.metadata()["omitted"], "4");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Summary<S> {
+    inner: S,
+    keep: usize,
+    first: Vec<Ereport>,
+    accepted: usize,
+    counts: BTreeMap<Severity, usize>,
+}
+
+impl<S> Summary<S>
+where
+    S: ReportSink,
+{
+    /// Passes reports on to `inner`, keeping the first `keep` of them for the summary.
+    pub fn new(inner: S, keep: usize) -> Self {
+        Self {
+            inner,
+            keep,
+            first: Vec::new(),
+            accepted: 0,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// The number of reports of each severity so far, counted like
+    /// `Ereport::count_by_severity`.
+    pub fn counts(&self) -> &BTreeMap<Severity, usize> {
+        &self.counts
+    }
+
+    /// Builds the summary, with the given message, and returns it together with the inner
+    /// sink.
+    ///
+    /// The summary has the highest severity seen, or `Severity::Note` if nothing was
+    /// reported. Its children are the reports which were kept, and its metadata holds the
+    /// count of each severity, and how many reports were `omitted` from the children.
+    #[track_caller]
+    pub fn finish<M>(self, msg: M) -> (Ereport, S)
+    where
+        M: Into<String>,
+    {
+        let severity = self.counts.keys().max().copied().unwrap_or(Severity::Note);
+        let mut metadata: BTreeMap<String, String> = self
+            .counts
+            .iter()
+            .map(|(severity, n)| (severity.as_str().to_string(), n.to_string()))
+            .collect();
+        metadata.insert(
+            "omitted".to_string(),
+            (self.accepted - self.first.len()).to_string(),
+        );
+
+        let rep = Ereport {
+            stack: self.first,
+            location: capture::location(),
+            severity,
+            metadata,
+            ..Ereport::bare(msg.into())
+        };
+        (rep, self.inner)
+    }
+}
+
+impl<S> ReportSink for Summary<S>
+where
+    S: ReportSink,
+{
+    fn accept(&mut self, rep: &Ereport) -> io::Result<()> {
+        self.inner.accept(rep)?;
+        self.accepted += 1;
+        for (severity, n) in rep.count_by_severity() {
+            *self.counts.entry(severity).or_insert(0) += n;
+        }
+        if self.first.len() < self.keep {
+            self.first.push(rep.clone());
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
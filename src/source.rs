@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::{Arc, RwLock};
+
+static SOURCES: RwLock<BTreeMap<String, Arc<str>>> = RwLock::new(BTreeMap::new());
+
+/// Registers the text of a named source, like a config file, for the whole process, so
+/// renderers can show the lines pointed at by a `Span`. Registering a name again replaces
+/// the text.
+///
+/// # Example
+/// ```rust
+/// use erep::{Ereport, Span};
+///
+/// erep::register_source("erep.toml", "name = \"erep\"\nport = \"abc\"\n");
+///
+/// let rep = Ereport::new("invalid config").push(
+///     Ereport::new("invalid port")
+///         .with_span(Span::new("erep.toml", 21..26).with_label("expected a number")),
+/// );
+///
+/// let expected = [
+///     "invalid config",
+///     "└── [1] invalid port",
+///     "         --> erep.toml:2:8",
+///     "          |",
+///     "        2 | port = \"abc\"",
+///     "          |        ^^^^^ expected a number",
+/// ];
+/// assert_eq!(rep.to_string(), expected.join("\n"));
+/// ```
+pub fn register_source<N, T>(name: N, text: T)
+where
+    N: Into<String>,
+    T: Into<Arc<str>>,
+{
+    SOURCES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name.into(), text.into());
+}
+
+/// The text of a source registered with `register_source`.
+pub fn source_text(name: &str) -> Option<Arc<str>> {
+    SOURCES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned()
+}
+
+/// A byte range in a named source, optionally with a label explaining what is wrong there.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    source: String,
+    start: usize,
+    end: usize,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    label: Option<String>,
+}
+
+impl Span {
+    pub fn new<S>(source: S, range: Range<usize>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            source: source.into(),
+            start: range.start,
+            end: range.end.max(range.start),
+            label: None,
+        }
+    }
+
+    pub fn with_label<L>(self, label: L) -> Self
+    where
+        L: Into<String>,
+    {
+        Self {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    /// The name of the source, as given to `register_source`.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+}
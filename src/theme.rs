@@ -0,0 +1,249 @@
+use std::fmt;
+use std::io::IsTerminal;
+
+use crate::Severity;
+
+/// A terminal color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// One of the 256 colors of the extended palette.
+    Fixed(u8),
+}
+
+impl Color {
+    fn write_fg(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Black => f.write_str("30"),
+            Color::Red => f.write_str("31"),
+            Color::Green => f.write_str("32"),
+            Color::Yellow => f.write_str("33"),
+            Color::Blue => f.write_str("34"),
+            Color::Magenta => f.write_str("35"),
+            Color::Cyan => f.write_str("36"),
+            Color::White => f.write_str("37"),
+            Color::Fixed(n) => write!(f, "38;5;{n}"),
+        }
+    }
+}
+
+/// How a piece of text is drawn, its color and attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    fg: Option<Color>,
+    bold: bool,
+    dimmed: bool,
+}
+
+impl Style {
+    /// A style which leaves the text as is.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bold: false,
+            dimmed: false,
+        }
+    }
+
+    pub const fn fg(self, color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub const fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    pub const fn dimmed(self) -> Self {
+        Self {
+            dimmed: true,
+            ..self
+        }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == Self::new()
+    }
+
+    /// Returns a value implementing `Display`, which wraps the text in the ANSI escape codes
+    /// of this style.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Color, Style};
+    ///
+    /// let style = Style::new().fg(Color::Red).bold();
+    ///
+    /// assert_eq!(style.paint("error").to_string(), "\x1b[1;31merror\x1b[0m");
+    /// assert_eq!(Style::new().paint("error").to_string(), "error");
+    /// ```
+    pub fn paint<D>(self, text: D) -> Painted<D>
+    where
+        D: fmt::Display,
+    {
+        Painted { style: self, text }
+    }
+}
+
+/// Text together with the style it is drawn in, created by `Style::paint`.
+#[derive(Debug, Clone, Copy)]
+pub struct Painted<D> {
+    style: Style,
+    text: D,
+}
+
+impl<D> fmt::Display for Painted<D>
+where
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.style.is_plain() {
+            return self.text.fmt(f);
+        }
+
+        // Empty text is left as is, so no escape codes are written for nothing.
+        let text = self.text.to_string();
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let mut codes = Vec::new();
+        if self.style.bold {
+            codes.push("1");
+        }
+        if self.style.dimmed {
+            codes.push("2");
+        }
+
+        write!(f, "\x1b[{}", codes.join(";"))?;
+        if let Some(fg) = self.style.fg {
+            if !codes.is_empty() {
+                f.write_str(";")?;
+            }
+            fg.write_fg(f)?;
+        }
+        write!(f, "m{text}\x1b[0m")
+    }
+}
+
+/// The styles a `Renderer` uses for the different parts of a report.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Theme {
+    pub error: Style,
+    pub warning: Style,
+    pub note: Style,
+    pub help: Style,
+    /// The message of each report.
+    pub message: Style,
+    /// The tree guides and the numbering of the entries.
+    pub guide: Style,
+    /// Locations, backtraces and the gutter of source snippets.
+    pub detail: Style,
+}
+
+impl Theme {
+    /// Bright colors, for terminals with a dark background. This is the default.
+    pub fn dark() -> Self {
+        Self {
+            error: Style::new().fg(Color::Red).bold(),
+            warning: Style::new().fg(Color::Yellow).bold(),
+            note: Style::new().fg(Color::Cyan).bold(),
+            help: Style::new().fg(Color::Green).bold(),
+            message: Style::new().bold(),
+            guide: Style::new().dimmed(),
+            detail: Style::new().fg(Color::Blue),
+        }
+    }
+
+    /// Darker colors, for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            error: Style::new().fg(Color::Fixed(124)).bold(),
+            warning: Style::new().fg(Color::Fixed(130)).bold(),
+            note: Style::new().fg(Color::Fixed(24)).bold(),
+            help: Style::new().fg(Color::Fixed(28)).bold(),
+            message: Style::new().bold(),
+            guide: Style::new().fg(Color::Fixed(245)),
+            detail: Style::new().fg(Color::Fixed(19)),
+        }
+    }
+
+    /// No colors, only bold and dimmed text.
+    pub fn monochrome() -> Self {
+        Self {
+            error: Style::new().bold(),
+            warning: Style::new().bold(),
+            note: Style::new().bold(),
+            help: Style::new().bold(),
+            message: Style::new().bold(),
+            guide: Style::new().dimmed(),
+            detail: Style::new().dimmed(),
+        }
+    }
+
+    /// The style of the severity label, and the underlines of source snippets.
+    pub fn severity(&self, severity: Severity) -> Style {
+        match severity {
+            Severity::Error => self.error,
+            Severity::Warning => self.warning,
+            Severity::Note => self.note,
+            Severity::Help => self.help,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// The output stream a report is written to, used to detect if colors should be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Whether to use colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorChoice {
+    /// Use colors if the stream is a terminal, unless overridden by the environment. Colors
+    /// are disabled if `NO_COLOR` is set to a non-empty value, otherwise they are enabled if
+    /// `CLICOLOR_FORCE` is set to anything but `0`.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether colors should be used when writing to the given stream.
+    pub fn enabled_for(self, stream: Stream) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                    false
+                } else if std::env::var_os("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+                    true
+                } else {
+                    match stream {
+                        Stream::Stdout => std::io::stdout().is_terminal(),
+                        Stream::Stderr => std::io::stderr().is_terminal(),
+                    }
+                }
+            }
+        }
+    }
+}
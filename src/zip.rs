@@ -0,0 +1,99 @@
+use crate::{Erep, Ereport};
+
+impl<T> Erep<T> {
+    /// Combines two independent values into a tuple, keeping the reports of both.
+    ///
+    /// Unlike `Erep::map`, the second value does not depend on the first, so both are always
+    /// computed, and every problem with either of them is reported.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Erep, Ereport};
+    ///
+    /// let host = Erep::with_report("localhost", Ereport::warning("`HOST` is not set"));
+    /// let port = Erep::with_report(8080, Ereport::new("invalid port `x`"));
+    ///
+    /// let (val, rep) = host.zip(port).unwrap_with_err();
+    /// let messages: Vec<_> = rep.as_ref().unwrap().children().iter().map(|r| r.message()).collect();
+    ///
+    /// assert_eq!(val, ("localhost", 8080));
+    /// assert_eq!(messages, ["`HOST` is not set", "invalid port `x`"]);
+    ///
+    /// let user = Erep::with_report("root", Ereport::note("running as `root`"));
+    /// let (val, rep) = Erep::zip3(
+    ///     Erep::with_report("localhost", Ereport::warning("`HOST` is not set")),
+    ///     Erep::ok(8080),
+    ///     user,
+    /// )
+    /// .unwrap_with_err();
+    /// let messages: Vec<_> = rep.as_ref().unwrap().children().iter().map(|r| r.message()).collect();
+    ///
+    /// assert_eq!(val, ("localhost", 8080, "root"));
+    /// assert_eq!(messages, ["`HOST` is not set", "running as `root`"]);
+    /// ```
+    #[track_caller]
+    pub fn zip<U>(self, other: Erep<U>) -> Erep<(T, U)> {
+        self.zip_with(other, |t, u| (t, u))
+    }
+
+    /// Combines two independent values with a function F, keeping the reports of both.
+    ///
+    /// # Example
+    /// ```rust
+    /// use erep::{Erep, Ereport};
+    ///
+    /// let a = Erep::with_report(1, Ereport::warning("`a` is deprecated"));
+    /// let b = Erep::with_report(2, Ereport::warning("`b` is deprecated"));
+    ///
+    /// let (sum, rep) = a.zip_with(b, |a, b| a + b).unwrap_with_err();
+    ///
+    /// assert_eq!(sum, 3);
+    /// assert_eq!(rep.unwrap().count_by_severity()[&erep::Severity::Warning], 2);
+    /// ```
+    #[track_caller]
+    pub fn zip_with<U, V, F>(self, other: Erep<U>, f: F) -> Erep<V>
+    where
+        F: FnOnce(T, U) -> V,
+    {
+        let (t, rt) = self.unwrap_with_err();
+        let (u, ru) = other.unwrap_with_err();
+
+        Erep {
+            val: f(t, u),
+            rep: Ereport::group(rt.into_iter().chain(ru).collect()),
+        }
+    }
+}
+
+macro_rules! impl_zip {
+    ($name:ident, $n:literal: $($v:ident: $t:ident),+) => {
+        impl<$($t),+> Erep<($($t,)+)> {
+            #[doc = concat!("Combines ", $n, " independent values into a tuple, keeping the reports of all of them, like `Erep::zip`.")]
+            #[track_caller]
+            #[allow(clippy::too_many_arguments)]
+            pub fn $name($($v: Erep<$t>),+) -> Self {
+                let mut reps = Vec::new();
+                $(
+                    let ($v, rep) = $v.unwrap_with_err();
+                    reps.extend(rep);
+                )+
+
+                Erep {
+                    val: ($($v,)+),
+                    rep: Ereport::group(reps),
+                }
+            }
+        }
+    };
+}
+
+impl_zip!(zip3, 3: a: A, b: B, c: C);
+impl_zip!(zip4, 4: a: A, b: B, c: C, d: D);
+impl_zip!(zip5, 5: a: A, b: B, c: C, d: D, e: E);
+impl_zip!(zip6, 6: a: A, b: B, c: C, d: D, e: E, f: F);
+impl_zip!(zip7, 7: a: A, b: B, c: C, d: D, e: E, f: F, g: G);
+impl_zip!(zip8, 8: a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H);
+impl_zip!(zip9, 9: a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H, i: I);
+impl_zip!(zip10, 10: a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H, i: I, j: J);
+impl_zip!(zip11, 11: a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H, i: I, j: J, k: K);
+impl_zip!(zip12, 12: a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H, i: I, j: J, k: K, l: L);
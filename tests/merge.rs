@@ -0,0 +1,164 @@
+use erep::{Erep, Ereport, Severity};
+use proptest::prelude::*;
+
+/// A report with one of a few messages, including the empty one, and any severity.
+fn leaf() -> impl Strategy<Value = Ereport> {
+    (
+        prop_oneof!["", "a", "b", "c"],
+        prop::sample::select(Severity::ALL.to_vec()),
+    )
+        .prop_map(|(msg, severity)| Ereport::new(msg).with_severity(severity))
+}
+
+/// Reports and grouping reports, nested a few levels deep.
+fn report() -> impl Strategy<Value = Ereport> {
+    let leaf = prop_oneof![3 => leaf(), 1 => Just(()).prop_map(|_| Ereport::empty())];
+
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        (inner.clone(), prop::collection::vec(inner, 0..3))
+            .prop_map(|(rep, children)| children.into_iter().fold(rep, Ereport::push))
+    })
+}
+
+fn opt_report() -> impl Strategy<Value = Option<Ereport>> {
+    prop::option::of(report())
+}
+
+/// The non-empty messages of every report, in order.
+fn messages(rep: &Ereport) -> Vec<String> {
+    let mut out = Vec::new();
+    if !rep.message().is_empty() {
+        out.push(rep.message().to_string());
+    }
+    for child in rep.children() {
+        out.extend(messages(child));
+    }
+    out
+}
+
+fn erep<T>(val: T, rep: Option<Ereport>) -> Erep<T> {
+    match rep {
+        Some(rep) => Erep::with_report(val, rep),
+        None => Erep::ok(val),
+    }
+}
+
+/// The number of reports which are not only a group, recursively.
+fn total(rep: &Option<Ereport>) -> usize {
+    rep.as_ref()
+        .map_or(0, |r| r.count_by_severity().values().sum())
+}
+
+fn all_messages(rep: &Option<Ereport>) -> Vec<String> {
+    rep.as_ref().map(messages).unwrap_or_default()
+}
+
+proptest! {
+    #[test]
+    fn merge_is_associative(a in report(), b in report(), c in report()) {
+        prop_assert_eq!(
+            a.clone().merge(b.clone()).merge(c.clone()),
+            a.merge(b.merge(c))
+        );
+    }
+
+    #[test]
+    fn empty_is_identity(a in report()) {
+        let merged = a.clone().merge(Ereport::empty());
+        prop_assert_eq!(&Ereport::empty().merge(a.clone()), &merged);
+        prop_assert_eq!(&merged.clone().merge(Ereport::empty()), &merged);
+        prop_assert_eq!(messages(&merged), messages(&a));
+    }
+
+    #[test]
+    fn merge_keeps_every_report(a in report(), b in report()) {
+        let merged = a.clone().merge(b.clone());
+
+        let mut expected = messages(&a);
+        expected.extend(messages(&b));
+        prop_assert_eq!(messages(&merged), expected);
+
+        let mut counts = a.count_by_severity();
+        for (severity, n) in b.count_by_severity() {
+            *counts.entry(severity).or_default() += n;
+        }
+        prop_assert_eq!(merged.count_by_severity(), counts);
+    }
+
+    #[test]
+    fn combine_is_none_only_when_empty(a in opt_report(), b in opt_report()) {
+        let nothing = a.as_ref().is_none_or(Ereport::is_empty)
+            && b.as_ref().is_none_or(Ereport::is_empty);
+        let combined = Ereport::combine(a.clone(), b.clone());
+
+        prop_assert_eq!(combined.is_none(), nothing);
+        prop_assert!(combined.as_ref().is_none_or(|r| !r.is_empty()));
+
+        let mut expected = all_messages(&a);
+        expected.extend(all_messages(&b));
+        prop_assert_eq!(all_messages(&combined), expected);
+        prop_assert_eq!(total(&combined), total(&a) + total(&b));
+    }
+
+    #[test]
+    fn leaves_without_a_message_survive(a in report(), leaf in leaf()) {
+        let empty = Ereport::new("").with_severity(leaf.severity());
+
+        let merged = a.clone().merge(empty.clone());
+        prop_assert_eq!(total(&Some(merged)), total(&Some(a.clone())) + 1);
+
+        let combined = Ereport::combine(None, Some(empty.clone()));
+        prop_assert_eq!(&combined, &Some(empty.clone()));
+        prop_assert!(!empty.is_empty());
+
+        let combined = Ereport::combine(Some(a.clone()), Some(empty));
+        prop_assert_eq!(total(&combined), total(&Some(a)) + 1);
+    }
+
+    #[test]
+    fn map_keeps_both_reports(a in opt_report(), b in opt_report()) {
+        let (_, rep) = erep((), a.clone())
+            .map(|_| erep((), b.clone()))
+            .unwrap_with_err();
+
+        prop_assert_eq!(rep, Ereport::combine(a, b));
+    }
+
+    #[test]
+    fn failing_emap_keeps_prior_report(a in opt_report()) {
+        let (val, rep) = erep("x", a.clone()).emap(|s| s.parse::<i32>(), None).unwrap_with_err();
+
+        prop_assert_eq!(val, None);
+
+        let mut expected = all_messages(&a);
+        expected.push("ParseIntError { kind: InvalidDigit }".to_string());
+        prop_assert_eq!(all_messages(&rep), expected);
+    }
+}
+
+#[test]
+fn clean_computations_stay_clean() {
+    let (val, rep) = Erep::ok(2)
+        .map(|i| Erep::ok(i * 2))
+        .emap(|i| Ok::<_, ()>(i + 1), None)
+        .omap(|i| i, None)
+        .zip(Erep::ok("x"))
+        .unwrap_with_err();
+
+    assert_eq!(val, (Some(5), "x"));
+    assert!(rep.is_none());
+}
+
+#[test]
+fn failures_without_a_message_are_reported() {
+    let erep = Erep::ok(1).emap(|_| Err::<i32, ()>(()), Some(String::new()));
+    assert!(!erep.is_clean());
+    assert!(erep.has_errors());
+
+    let rep = Ereport::new("x").merge(Ereport::warning(""));
+    assert_eq!(rep.count_by_severity()[&Severity::Warning], 1);
+
+    let mut acc = erep::Accumulator::new();
+    acc.check_opt(None::<i32>, "");
+    assert!(acc.finish(()).has_errors());
+}